@@ -55,6 +55,12 @@ impl Ord for Employee {
 	}
 }
 
+/// A comparator for sorting employees by seniority: most experienced first, and by ascending `uid`
+/// among those with the same experience. Unlike [`Ord`], this is usable with `sort_by`.
+pub fn by_seniority(a: &Employee, b: &Employee) -> std::cmp::Ordering {
+	b.experience.cmp(&a.experience).then(a.uid.cmp(&b.uid))
+}
+
 // We want to parse employee information from a string data
 // The string data should be comma separated. Here are a few examples:
 //
@@ -91,17 +97,111 @@ impl From<Employee> for String {
 	}
 }
 
+// For logging, we want a human readable summary, such as "John (#42): 60mo exp, wage 77".
+impl std::fmt::Display for Employee {
+	fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+		write!(f, "{} (#{}): {}mo exp, wage {}", self.name, self.uid, self.experience, self.wage)
+	}
+}
+
+impl Employee {
+	/// Promote this employee, adding `extra_experience` to their experience and `raise` to their
+	/// wage. Both saturate at `u32::MAX`.
+	pub fn promote(&mut self, extra_experience: u32, raise: u32) {
+		self.experience = self.experience.saturating_add(extra_experience);
+		self.wage = self.wage.saturating_add(raise);
+	}
+
+	/// Clamp this employee's wage into the band `[min, max]`. Panics if `min > max`.
+	pub fn clamp_wage(&mut self, min: u32, max: u32) {
+		self.wage = self.wage.clamp(min, max);
+	}
+}
+
+/// The wage recommended for an employee with `experience` months of experience: a `base` wage,
+/// plus `per_year` for each full year of experience. Saturates at `u32::MAX`.
+pub fn recommended_wage(experience: u32, base: u32, per_year: u32) -> u32 {
+	base.saturating_add((experience / 12).saturating_mul(per_year))
+}
+
+/// Sum up the wages and experience of all of `emps`, returned as `(total_wage, total_experience)`.
+///
+/// The totals are accumulated in `u64`, so that they cannot overflow for any realistic team size.
+pub fn payroll_totals(emps: &[Employee]) -> (u64, u64) {
+	emps.iter().fold((0, 0), |(wage, experience), e| {
+		(wage + e.wage as u64, experience + e.experience as u64)
+	})
+}
+
+/// The reasons for which [`employee_from_bytes`] can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+	/// The input ends before the encoded employee does.
+	Truncated,
+	/// The input continues after the encoded employee.
+	TrailingBytes,
+	/// The encoded name is not valid UTF-8.
+	InvalidName,
+}
+
+/// Encode `e` into bytes, e.g. for caching: the length of the name and the name itself, followed
+/// by the `uid`, `experience` and `wage`. All numbers are little-endian `u32`s.
+pub fn employee_to_bytes(e: &Employee) -> Vec<u8> {
+	let mut bytes = Vec::with_capacity(16 + e.name.len());
+	bytes.extend_from_slice(&(e.name.len() as u32).to_le_bytes());
+	bytes.extend_from_slice(e.name.as_bytes());
+	for field in [e.uid, e.experience, e.wage] {
+		bytes.extend_from_slice(&field.to_le_bytes());
+	}
+	bytes
+}
+
+/// Decode an employee previously encoded by [`employee_to_bytes`].
+pub fn employee_from_bytes(b: &[u8]) -> Result<Employee, DecodeError> {
+	fn take<'a>(b: &mut &'a [u8], len: usize) -> Result<&'a [u8], DecodeError> {
+		if b.len() < len {
+			return Err(DecodeError::Truncated)
+		}
+		let (taken, rest) = b.split_at(len);
+		*b = rest;
+		Ok(taken)
+	}
+	fn take_u32(b: &mut &[u8]) -> Result<u32, DecodeError> {
+		let bytes = take(b, 4)?;
+		Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+	}
+
+	let mut b = b;
+	let name_len = take_u32(&mut b)? as usize;
+	let name = take(&mut b, name_len)?;
+	let name = String::from_utf8(name.to_vec()).map_err(|_| DecodeError::InvalidName)?;
+	let uid = take_u32(&mut b)?;
+	let experience = take_u32(&mut b)?;
+	let wage = take_u32(&mut b)?;
+	if !b.is_empty() {
+		return Err(DecodeError::TrailingBytes)
+	}
+
+	Ok(Employee { name, experience, wage, uid })
+}
+
+/// This function is not graded. It is just for collecting feedback.
+/// The answers to both of the questions below, gathered in one [`crate::Feedback`].
+pub fn feedback() -> crate::Feedback {
+	crate::Feedback { difficulty: 150, hours: 1 }
+}
+
 /// This function is not graded. It is just for collecting feedback.
 /// On a scale from 0 - 255, with zero being extremely easy and 255 being extremely hard,
 /// how hard did you find this section of the exam.
 pub fn how_hard_was_this_section() -> u8 {
-	150
+	feedback().difficulty
 }
 
 /// This function is not graded. It is just for collecting feedback.
 /// How much time (in hours) did you spend on this section of the exam?
 pub fn how_many_hours_did_you_spend_on_this_section() -> u8 {
-	1
+	feedback().hours
 }
 
 #[cfg(test)]
@@ -186,4 +286,148 @@ mod tests {
 
 		assert!(billy_ == billy);
 	}
+
+	#[test]
+	fn payroll_totals_small_team() {
+		let team = vec![
+			Employee { name: String::from("Billy"), experience: 4, wage: 5, uid: 345 },
+			Employee { name: String::from("Susie"), experience: 5, wage: 7, uid: 347 },
+		];
+		assert_eq!(payroll_totals(&team), (12, 9));
+		assert_eq!(payroll_totals(&[]), (0, 0));
+	}
+
+	#[test]
+	fn payroll_totals_no_overflow() {
+		let team = vec![
+			Employee { name: String::from("Billy"), experience: u32::MAX, wage: u32::MAX, uid: 1 },
+			Employee { name: String::from("Susie"), experience: u32::MAX, wage: 1, uid: 2 },
+		];
+		assert_eq!(
+			payroll_totals(&team),
+			(u32::MAX as u64 + 1, 2 * u32::MAX as u64)
+		);
+	}
+
+	#[test]
+	fn employee_display() {
+		let john = Employee {
+			name: String::from("John"),
+			experience: 60,
+			wage: 77,
+			uid: 42,
+		};
+
+		assert_eq!(john.to_string(), "John (#42): 60mo exp, wage 77");
+	}
+
+	#[test]
+	fn employee_promote() {
+		let mut billy = Employee {
+			name: String::from("Billy"),
+			experience: 4,
+			wage: 5,
+			uid: 345,
+		};
+
+		billy.promote(12, 2);
+		billy.promote(6, 1);
+		assert_eq!((billy.experience, billy.wage), (22, 8));
+
+		billy.wage = u32::MAX - 1;
+		billy.promote(u32::MAX, 10);
+		assert_eq!((billy.experience, billy.wage), (u32::MAX, u32::MAX));
+	}
+
+	#[test]
+	fn sort_by_seniority() {
+		let employee = |name: &str, experience, uid| Employee {
+			name: String::from(name),
+			experience,
+			wage: 5,
+			uid,
+		};
+		let mut team = [
+			employee("Billy", 4, 345),
+			employee("Susie", 12, 347),
+			employee("Jose", 12, 1),
+			employee("Anna", 0, 2),
+		];
+
+		team.sort_by(by_seniority);
+		let names = team.iter().map(|e| e.name.as_str()).collect::<Vec<_>>();
+		assert_eq!(names, vec!["Jose", "Susie", "Billy", "Anna"]);
+	}
+
+	#[test]
+	fn employee_clamp_wage() {
+		let mut billy = Employee {
+			name: String::from("Billy"),
+			experience: 4,
+			wage: 5,
+			uid: 345,
+		};
+
+		billy.clamp_wage(10, 20);
+		assert_eq!(billy.wage, 10);
+		billy.clamp_wage(15, 20);
+		assert_eq!(billy.wage, 15);
+		billy.clamp_wage(10, 20);
+		assert_eq!(billy.wage, 15);
+		billy.clamp_wage(0, 12);
+		assert_eq!(billy.wage, 12);
+	}
+
+	#[test]
+	fn recommended_wage_by_years() {
+		assert_eq!(recommended_wage(0, 10, 2), 10);
+		assert_eq!(recommended_wage(11, 10, 2), 10);
+		assert_eq!(recommended_wage(12, 10, 2), 12);
+		assert_eq!(recommended_wage(60, 10, 2), 20);
+		assert_eq!(recommended_wage(u32::MAX, 10, u32::MAX), u32::MAX);
+		assert_eq!(recommended_wage(24, u32::MAX, 1), u32::MAX);
+	}
+
+	#[test]
+	fn employee_bytes_roundtrip() {
+		let billy = Employee {
+			name: String::from("Billy"),
+			experience: 4,
+			wage: 5,
+			uid: 345,
+		};
+
+		let bytes = employee_to_bytes(&billy);
+		assert_eq!(bytes.len(), 4 + 5 + 12);
+		let decoded = employee_from_bytes(&bytes).unwrap();
+		assert_eq!(decoded.name, "Billy");
+		assert_eq!((decoded.uid, decoded.experience, decoded.wage), (345, 4, 5));
+
+		let nameless = Employee { name: String::new(), experience: 0, wage: u32::MAX, uid: 1 };
+		let decoded = employee_from_bytes(&employee_to_bytes(&nameless)).unwrap();
+		assert_eq!((decoded.name.as_str(), decoded.wage), ("", u32::MAX));
+	}
+
+	#[test]
+	fn employee_bytes_malformed() {
+		let billy = Employee {
+			name: String::from("Billy"),
+			experience: 4,
+			wage: 5,
+			uid: 345,
+		};
+		let bytes = employee_to_bytes(&billy);
+
+		for len in 0..bytes.len() {
+			assert_eq!(employee_from_bytes(&bytes[..len]), Err(DecodeError::Truncated));
+		}
+
+		let mut trailing = bytes.clone();
+		trailing.push(0);
+		assert_eq!(employee_from_bytes(&trailing), Err(DecodeError::TrailingBytes));
+
+		let mut invalid = bytes;
+		invalid[4] = 0xff;
+		assert_eq!(employee_from_bytes(&invalid), Err(DecodeError::InvalidName));
+	}
 }
@@ -76,17 +76,23 @@ impl Iterator for Fibonacci {
 	}
 }
 
+/// This function is not graded. It is just for collecting feedback.
+/// The answers to both of the questions below, gathered in one [`crate::Feedback`].
+pub fn feedback() -> crate::Feedback {
+	crate::Feedback { difficulty: 150, hours: 2 }
+}
+
 /// This function is not graded. It is just for collecting feedback.
 /// On a scale from 0 - 255, with zero being extremely easy and 255 being extremely hard,
 /// how hard did you find this section of the exam.
 pub fn how_hard_was_this_section() -> u8 {
-	150
+	feedback().difficulty
 }
 
 /// This function is not graded. It is just for collecting feedback.
 /// How much time (in hours) did you spend on this section of the exam?
 pub fn how_many_hours_did_you_spend_on_this_section() -> u8 {
-	2
+	feedback().hours
 }
 
 #[cfg(test)]
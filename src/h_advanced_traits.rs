@@ -39,6 +39,111 @@ impl From<BTU> for Calorie {
 	}
 }
 
+/// The reasons for which converting between energy units can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConversionError {
+	/// The converted value does not fit in a `u32`.
+	Overflow,
+	/// The value to convert is not a number at all.
+	NotANumber,
+	/// The value to convert is negative, while energy cannot be.
+	Negative,
+}
+
+/// `b` as an `f64`, for interop with float-based math. This is lossless.
+pub fn btu_to_f64(b: BTU) -> f64 {
+	b as f64
+}
+
+/// `x` as a `BTU`, rounded down. Fails for NaN, negative values, and values above `BTU::MAX`,
+/// including infinities, which overflow.
+pub fn btu_from_f64(x: f64) -> Result<BTU, ConversionError> {
+	if x.is_nan() {
+		Err(ConversionError::NotANumber)
+	} else if x < 0.0 {
+		Err(ConversionError::Negative)
+	} else if x > BTU::MAX as f64 {
+		Err(ConversionError::Overflow)
+	} else {
+		Ok(x as BTU)
+	}
+}
+
+/// Same as `Calorie::from(b)`, but fails instead of overflowing.
+///
+/// A `Calorie` can hold up to `u32::MAX` calories, so that the largest convertible value is
+/// `u32::MAX / 251 = 17_111_423` BTU.
+pub fn to_calorie_checked(b: BTU) -> Result<Calorie, ConversionError> {
+	b.checked_mul(251).map(Calorie).ok_or(ConversionError::Overflow)
+}
+
+/// A unit of energy that is a fixed multiple of [`BTU`], such as [`Joule`] and [`Calorie`].
+pub trait EnergyUnit: Into<BTU> + From<BTU> + Copy {
+	/// How many of this unit make up a single BTU.
+	const PER_BTU: u32;
+
+	/// Wrap a raw value, expressed in this unit.
+	fn from_raw(raw: u32) -> Self;
+
+	/// The raw value, expressed in this unit.
+	fn raw(self) -> u32;
+}
+
+impl EnergyUnit for Joule {
+	const PER_BTU: u32 = 1055;
+	fn from_raw(raw: u32) -> Self {
+		Joule(raw)
+	}
+	fn raw(self) -> u32 {
+		self.0
+	}
+}
+
+impl EnergyUnit for Calorie {
+	const PER_BTU: u32 = 251;
+	fn from_raw(raw: u32) -> Self {
+		Calorie(raw)
+	}
+	fn raw(self) -> u32 {
+		self.0
+	}
+}
+
+/// The integer mean of `readings`, or `Joule(0)` if there are none.
+pub fn average_joules(readings: &[Joule]) -> Joule {
+	if readings.is_empty() {
+		return Joule(0)
+	}
+
+	let total: u64 = readings.iter().map(|j| j.0 as u64).sum();
+	Joule((total / readings.len() as u64) as u32)
+}
+
+/// The sum of `a` and `b`, which may be in different units, expressed in the unit `R`.
+///
+/// Both are converted to BTU (rounding down) before being added, and the sum saturates at
+/// `BTU::MAX`.
+pub fn add_energy<A: Into<BTU>, B: Into<BTU>, R: From<BTU>>(a: A, b: B) -> R {
+	let total = a.into() as u64 + b.into() as u64;
+	R::from(total.min(BTU::MAX as u64) as BTU)
+}
+
+/// The energy, in joules, lost to truncation when converting `start` from [`Joule`] to [`BTU`] and
+/// back, `hops` times in a row.
+///
+/// Only the first hop truncates anything: after that, the energy is a whole number of BTU, which
+/// converts back and forth exactly. The loss is therefore bounded by one BTU, i.e. 1054 joules.
+pub fn chain_loss(start: Joule, hops: u32) -> u32 {
+	let end = (0..hops).fold(start, |joules, _| Joule::from(BTU::from(joules)));
+	start.0 - end.0
+}
+
+/// The energy delivered by a power of `watts` over `seconds`, saturating at `u32::MAX` joules.
+pub fn energy_from_power(watts: u32, seconds: u32) -> Joule {
+	let joules = watts as u64 * seconds as u64;
+	Joule(joules.min(u32::MAX as u64) as u32)
+}
+
 // Now, we start defining some types of fuel.
 
 /// A technology for storing energy for later consumption.
@@ -50,13 +155,38 @@ pub trait Fuel {
 
 	/// The amount of energy contained in a single unit of fuel.
 	fn energy_density() -> Self::Output;
+
+	/// The CO2-equivalent emitted by burning a single unit of fuel. Renewables emit nothing.
+	fn co2_per_unit() -> u32 {
+		0
+	}
+
+	/// The price of a single unit of fuel. Fuels that are not traded, are free.
+	fn cost_per_unit() -> u32 {
+		0
+	}
+
+	/// A human readable name of the fuel, e.g. for reports. By default, this is the full name of
+	/// the type.
+	fn name() -> &'static str {
+		std::any::type_name::<Self>()
+	}
 }
 
 pub struct Diesel;
 impl Fuel for Diesel {
 	type Output = Joule;
 	fn energy_density() -> Self::Output {
-		Joule::from(100 as BTU)
+		Joule::from(Self::ENERGY_DENSITY_BTU)
+	}
+	fn name() -> &'static str {
+		"Diesel"
+	}
+	fn cost_per_unit() -> u32 {
+		3
+	}
+	fn co2_per_unit() -> u32 {
+		74
 	}
 }
 
@@ -64,7 +194,13 @@ pub struct LithiumBattery;
 impl Fuel for LithiumBattery {
 	type Output = Calorie;
 	fn energy_density() -> Self::Output {
-		Calorie::from(200 as BTU)
+		Calorie::from(Self::ENERGY_DENSITY_BTU)
+	}
+	fn name() -> &'static str {
+		"LithiumBattery"
+	}
+	fn cost_per_unit() -> u32 {
+		8
 	}
 }
 
@@ -72,8 +208,219 @@ pub struct Uranium;
 impl Fuel for Uranium {
 	type Output = Joule;
 	fn energy_density() -> Self::Output {
-		Joule::from(1000 as BTU)
+		Joule::from(Self::ENERGY_DENSITY_BTU)
+	}
+	fn name() -> &'static str {
+		"Uranium"
+	}
+	fn cost_per_unit() -> u32 {
+		25
+	}
+	fn co2_per_unit() -> u32 {
+		12
+	}
+}
+
+pub struct Hydrogen;
+impl Fuel for Hydrogen {
+	type Output = BTU;
+	fn energy_density() -> Self::Output {
+		Self::ENERGY_DENSITY_BTU
+	}
+	fn name() -> &'static str {
+		"Hydrogen"
+	}
+	fn cost_per_unit() -> u32 {
+		6
+	}
+}
+
+/// No fuel at all, e.g. for an empty slot: `FuelContainer<()>` never yields any energy.
+impl Fuel for () {
+	type Output = BTU;
+	fn energy_density() -> Self::Output {
+		0
+	}
+}
+
+thread_local! {
+	static DYNAMIC_DENSITY: std::cell::Cell<BTU> = const { std::cell::Cell::new(0) };
+}
+
+/// A fuel whose energy density is only known at runtime, e.g. for parameter sweeps. As
+/// `energy_density` takes no `self`, the density is read from a thread-local, set through
+/// [`set_dynamic_density`].
+///
+/// NOTE: being thread-local, the density is per thread: setting it on one thread does not affect
+/// any other, and each new thread starts off with a density of zero.
+pub struct DynamicFuel;
+impl Fuel for DynamicFuel {
+	type Output = BTU;
+	fn energy_density() -> Self::Output {
+		DYNAMIC_DENSITY.with(|density| density.get())
+	}
+	fn name() -> &'static str {
+		"DynamicFuel"
+	}
+}
+
+/// Set the energy density of [`DynamicFuel`], on the current thread only.
+pub fn set_dynamic_density(b: BTU) {
+	DYNAMIC_DENSITY.with(|density| density.set(b));
+}
+
+/// A [`Fuel`] whose energy density is known at compile time, such that it can be used in `const`
+/// contexts.
+pub trait ConstDensity: Fuel {
+	/// The same as [`Fuel::energy_density`], in BTU.
+	const ENERGY_DENSITY_BTU: BTU;
+}
+
+impl ConstDensity for Diesel {
+	const ENERGY_DENSITY_BTU: BTU = 100;
+}
+
+impl ConstDensity for LithiumBattery {
+	const ENERGY_DENSITY_BTU: BTU = 200;
+}
+
+impl ConstDensity for Uranium {
+	const ENERGY_DENSITY_BTU: BTU = 1000;
+}
+
+impl ConstDensity for Hydrogen {
+	const ENERGY_DENSITY_BTU: BTU = 300;
+}
+
+/// The fuels that we know of, for when the fuel type is only known at runtime.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FuelKind {
+	Diesel,
+	LithiumBattery,
+	Uranium,
+	Hydrogen,
+}
+
+impl FuelKind {
+	/// The same as [`ConstDensity::ENERGY_DENSITY_BTU`] of the corresponding fuel type.
+	pub fn energy_density_btu(self) -> BTU {
+		match self {
+			FuelKind::Diesel => Diesel::ENERGY_DENSITY_BTU,
+			FuelKind::LithiumBattery => LithiumBattery::ENERGY_DENSITY_BTU,
+			FuelKind::Uranium => Uranium::ENERGY_DENSITY_BTU,
+			FuelKind::Hydrogen => Hydrogen::ENERGY_DENSITY_BTU,
+		}
+	}
+
+	/// The same as [`Fuel::cost_per_unit`] of the corresponding fuel type.
+	pub fn cost_per_unit(self) -> u32 {
+		match self {
+			FuelKind::Diesel => Diesel::cost_per_unit(),
+			FuelKind::LithiumBattery => LithiumBattery::cost_per_unit(),
+			FuelKind::Uranium => Uranium::cost_per_unit(),
+			FuelKind::Hydrogen => Hydrogen::cost_per_unit(),
+		}
+	}
+
+	/// The one byte tag of this kind, as used by [`to_bytes`].
+	pub fn tag(self) -> u8 {
+		match self {
+			FuelKind::Diesel => 0,
+			FuelKind::LithiumBattery => 1,
+			FuelKind::Uranium => 2,
+			FuelKind::Hydrogen => 3,
+		}
+	}
+
+	/// The inverse of [`FuelKind::tag`], if `tag` belongs to any kind at all.
+	pub fn from_tag(tag: u8) -> Option<Self> {
+		match tag {
+			0 => Some(FuelKind::Diesel),
+			1 => Some(FuelKind::LithiumBattery),
+			2 => Some(FuelKind::Uranium),
+			3 => Some(FuelKind::Hydrogen),
+			_ => None,
+		}
 	}
+
+	/// The number of units of this fuel that store at least `target` BTU.
+	pub fn units_for_target(self, target: BTU) -> u32 {
+		let density = self.energy_density_btu();
+		target / density + (target % density != 0) as u32
+	}
+}
+
+/// A [`Fuel`] type that is one of the [`FuelKind`]s.
+pub trait KnownFuel: Fuel {
+	const KIND: FuelKind;
+}
+
+impl KnownFuel for Diesel {
+	const KIND: FuelKind = FuelKind::Diesel;
+}
+
+impl KnownFuel for LithiumBattery {
+	const KIND: FuelKind = FuelKind::LithiumBattery;
+}
+
+impl KnownFuel for Uranium {
+	const KIND: FuelKind = FuelKind::Uranium;
+}
+
+impl KnownFuel for Hydrogen {
+	const KIND: FuelKind = FuelKind::Hydrogen;
+}
+
+/// The cheapest of `kinds` to store at least `target` BTU with, along with the number of units
+/// needed and their total cost (saturating at `u32::MAX`). Ties go to the first one in `kinds`.
+///
+/// Returns `None` if there are no `kinds` to choose from.
+pub fn cheapest_for_target(target: BTU, kinds: &[FuelKind]) -> Option<(FuelKind, u32, u32)> {
+	kinds
+		.iter()
+		.map(|&kind| {
+			let units = kind.units_for_target(target);
+			(kind, units, units.saturating_mul(kind.cost_per_unit()))
+		})
+		.min_by_key(|&(_, _, cost)| cost)
+}
+
+/// The cost of the fuel needed for one more BTU, on top of what `amount` units of `F` store.
+///
+/// As the energy stored is linear in the amount, this is the same for any `amount`, namely the
+/// [`Fuel::cost_per_unit`] over the energy density. Thus, `amount` is only taken so that callers
+/// doing economic dispatch can pass their current amount, but is otherwise ignored. Returns `None`
+/// for free fuels and fuels that store no energy, where the marginal cost is meaningless.
+pub fn marginal_cost<F: Fuel>(_amount: u32) -> Option<f32> {
+	let density = BTU::from(F::energy_density().into());
+	let cost = F::cost_per_unit();
+	if density == 0 || cost == 0 {
+		return None
+	}
+
+	Some(cost as f32 / density as f32)
+}
+
+/// The number of periods until a renewable provider, which needs no fuel, pays back its upfront
+/// `renewable_upfront` cost, compared to burning `amount_per_period` units of a fossil fuel costing
+/// `fossil_cost_per_unit` per period.
+///
+/// Returns `u32::MAX` if it never pays back, because burning the fossil fuel costs nothing.
+pub fn payback_units(
+	renewable_upfront: u32,
+	fossil_cost_per_unit: u32,
+	amount_per_period: u32,
+) -> u32 {
+	let savings = fossil_cost_per_unit as u64 * amount_per_period as u64;
+	if renewable_upfront == 0 {
+		return 0
+	}
+	if savings == 0 {
+		return u32::MAX
+	}
+
+	let upfront = renewable_upfront as u64;
+	(upfront / savings + (upfront % savings != 0) as u64) as u32
 }
 
 /// A container for any fuel type.
@@ -97,304 +444,2883 @@ impl<F: Fuel> FuelContainer<F> {
 	}
 }
 
-/// Something that can provide energy from a given `F` fuel type, like a power-plant.
-pub trait ProvideEnergy<F: Fuel> {
-	/// Consume the fuel container and return the created energy, based on the power density of the
-	/// fuel and potentially other factors.
-	///
-	/// Some fuel providers might have some kind of decay or inefficiency, which should be reflected
-	/// here. Otherwise, [ProvideEnergy::provide_energy_with_efficiency] or
-	/// [ProvideEnergy::provide_energy_ideal] might be good enough.
-	///
-	/// Not all `ProvideEnergy` implementations need to have internal state. Therefore, this
-	/// interface accepts `&self`, not `&mut self`. You might need to use special language features
-	/// to overcome this.
-	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output;
+/// A unique type explicitly representing a [`FuelContainerBuilder`] whose fuel is not chosen yet.
+pub struct NoFuel;
 
-	/// Convert the amount of fuel in `f` with an exact efficiency of `e`.
-	///
-	/// NOTE: all efficiencies are interpreted as u8 values that can be at most 100, and represent a
-	/// percent. If an efficiency above 100 is supplied, the code should treat it as 100. That is to
-	/// say that the efficiency is "saturating" at 100%.
-	///
-	/// This method must be provided as it will be the same in all implementations.
-	fn provide_energy_with_efficiency(&self, f: FuelContainer<F>, e: u8) -> <F as Fuel>::Output {
-		let efficiency = e.min(100) as f32 / 100.0;  // Convert to percentage and clamp to 100%
-        let energy_density = F::energy_density();    // Get energy density from the Fuel trait
-        let total_energy = BTU::from(energy_density.into()) * f.amount;  // Total energy in BTUs
-        let adjusted_energy = (total_energy as f32 * efficiency).round() as u32; // Adjust for efficiency and round off
+/// A builder for [`FuelContainer`]s, using the same "type-state" pattern as the
+/// `TypedEmployeeBuilder`: a container can only be built once its fuel type has been chosen.
+///
+/// ## Example
+///
+/// ```
+/// use pba_qualifier_exam::h_advanced_traits::{Diesel, FuelContainer, FuelContainerBuilder};
+///
+/// # fn main() {
+/// let container: FuelContainer<Diesel> =
+///     FuelContainerBuilder::new().fuel::<Diesel>().amount(10).build();
+/// # }
+/// ```
+pub struct FuelContainerBuilder<F> {
+	amount: u32,
+	_marker: PhantomData<F>,
+}
 
-        // Convert back to the fuel's output unit
-        <F as Fuel>::Output::from(adjusted_energy)
+impl FuelContainerBuilder<NoFuel> {
+	pub fn new() -> Self {
+		Self {
+			amount: 0,
+			_marker: Default::default(),
+		}
 	}
 
-	/// Same as [`ProvideEnergy::provide_energy_with_efficiency`], but with an efficiency of 100.
-	///
-	/// This method must be provided as it will be the same in all implementations.
-	fn provide_energy_ideal(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
-		self.provide_energy_with_efficiency(f, 100) 
+	pub fn fuel<F: Fuel>(self) -> FuelContainerBuilder<F> {
+		FuelContainerBuilder {
+			amount: self.amount,
+			_marker: Default::default(),
+		}
 	}
 }
 
-/// A nuclear reactor that can only consume `Uranium` and provide energy with 99% efficiency.
-pub struct NuclearReactor;
-impl ProvideEnergy<Uranium> for NuclearReactor {
-	fn provide_energy(&self, f: FuelContainer<Uranium>) -> <Uranium as Fuel>::Output {
-		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
-		let efficiency = 99; // 99% efficiency for the nuclear reactor
-        self.provide_energy_with_efficiency(f, efficiency)
+impl Default for FuelContainerBuilder<NoFuel> {
+	fn default() -> Self {
+		Self::new()
 	}
 }
 
-/// A combustion engine that can only consume `Diesel`.
-///
-/// The `DECAY` const must be interpreted as such: per every `DECAY` times `provide_energy` is
-/// called on an instance of this type, the efficiency should reduce by one. The initial efficiency
-/// must be configurable with a `fn new(efficiency: u8) -> Self`.
-pub struct InternalCombustion<const DECAY: u32>{/* Fill the fields as needed */
-	efficiency: RefCell<u8>,
-    call_count: RefCell<u32>,
+impl<F> FuelContainerBuilder<F> {
+	pub fn amount(mut self, amount: u32) -> Self {
+		self.amount = amount;
+		self
+	}
 }
 
-impl<const DECAY: u32> InternalCombustion<DECAY> {
-	pub fn new(efficiency: u8) -> Self {
-		InternalCombustion {
-            efficiency: RefCell::new(efficiency.min(100)),
-            call_count: RefCell::new(0),
-        }
+impl<F: Fuel> FuelContainerBuilder<F> {
+	pub fn build(self) -> FuelContainer<F> {
+		FuelContainer::new(self.amount)
 	}
 }
 
-impl<const DECAY: u32> ProvideEnergy<Diesel> for InternalCombustion<DECAY> {
-	fn provide_energy(&self, f: FuelContainer<Diesel>) -> <Diesel as Fuel>::Output {
-		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
-		let mut current_count = self.call_count.borrow_mut();
-		let mut current_efficiency = self.efficiency.borrow_mut();
-		//println!("{}", *current_efficiency);
-
-        if *current_count % DECAY == 0 && *current_efficiency > 1 && *current_count>=DECAY{
-                *current_efficiency -= 1;
-				//println!("Inside {}", *current_efficiency);
-            
-        }
-		*current_count += 1;
-        
-        self.provide_energy_with_efficiency(f, *current_efficiency)
-	}
+/// A container of fuel `F` measured by its mass, in grams, rather than by abstract units.
+pub struct MassContainer<F: Fuel> {
+	grams: u32,
+	_m: PhantomData<F>,
 }
 
-/// A hypothetical device that can, unlike the `InternalCombustion`, consume **any fuel** that's of
-/// type `trait Fuel`. It can provide a fixed efficiency regardless of fuel type. As before,
-/// EFFICIENCY is a u8 whose value should not exceed 100, is interpreted as a percent, and should
-/// saturate at 100% when a higher value is supplied.
-pub struct OmniGenerator<const EFFICIENCY: u8>;
+impl<F: Fuel> MassContainer<F> {
+	pub fn new(grams: u32) -> Self {
+		Self {
+			grams,
+			_m: Default::default(),
+		}
+	}
 
-// NOTE: implement `ProvideEnergy` for `OmniGenerator` using only one `impl` block.
-impl<const EFFICIENCY: u8, F: Fuel> ProvideEnergy<F> for OmniGenerator<EFFICIENCY> {
-	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
-		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
-		self.provide_energy_with_efficiency(f, EFFICIENCY)
+	/// Convert to a [`FuelContainer`] holding as many whole units as fit in the mass of this one,
+	/// where each unit weighs `grams_per_unit`. Units without any mass do not make up any fuel.
+	pub fn into_units(self, grams_per_unit: u32) -> FuelContainer<F> {
+		FuelContainer::new(self.grams.checked_div(grams_per_unit).unwrap_or(0))
 	}
 }
 
-/// A type that can wrap two different fuel types and mix them together.
-///
-/// The energy density of the new fuel type is the average of the two given, once converted to BTU.
-/// The output unit should also be BTU.
-///
-/// This can represent a new fuel type, thus it must implement `Fuel`.
-pub struct Mixed<F1: Fuel, F2: Fuel>(PhantomData<(F1, F2)>);
+/// The energy density of `F` per gram rather than per unit, rounded down, where each unit weighs
+/// `grams_per_unit`. Units without any mass have no meaningful density per gram, hence `None`.
+pub fn density_per_gram<F: Fuel>(grams_per_unit: u32) -> Option<BTU> {
+	BTU::from(F::energy_density().into()).checked_div(grams_per_unit)
+}
 
-impl<F1: Fuel, F2: Fuel> Fuel for Mixed<F1, F2> {
-	type Output = BTU;
+/// A tank that can hold at most `CAP` units of fuel `F`.
+pub struct FixedTank<const CAP: u32, F: Fuel> {
+	amount: u32,
+	_marker: PhantomData<F>,
+}
 
-	fn energy_density() -> Self::Output {
-		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
-		let energy_density1 = BTU::from(F1::energy_density().into());
-        let energy_density2 = BTU::from(F2::energy_density().into());
+impl<const CAP: u32, F: Fuel> FixedTank<CAP, F> {
+	/// Create a tank holding `amount` units of fuel, which is clamped to `CAP`.
+	pub fn new(amount: u32) -> Self {
+		Self {
+			amount: amount.min(CAP),
+			_marker: Default::default(),
+		}
+	}
 
-        (energy_density1 + energy_density2) / 2
+	/// The amount of fuel in the tank.
+	pub fn amount(&self) -> u32 {
+		self.amount
 	}
 }
 
-// Now think about how you can make the mixer configurable, such that it would produce a new fuel
-// with an energy density that is more influences by one type than the other.
-//
-// For example, you have a mixer of F1, F2, and some coefficient C1, where the energy density of the
-// mixture is `F1 * C1 + F2 * (1 - C1) )` where `C1` is a ratio (which you have to represent again
-// with a u8 percent).
-//
-// The main trick is to overcome the fact that `fn energy_density` does not take in a `self`, so the
-// coefficients need to be incorporated in some other way (you've already seen examples of that in
-// this file ;)).
-pub struct CustomMixed<const C: u8, F1, F2>(PhantomData<(F1, F2)>);
-impl<const C: u8, F1: Fuel, F2: Fuel> Fuel for CustomMixed<C, F1, F2> {
-	type Output = BTU;
+impl<const CAP: u32, F: ConstDensity> FixedTank<CAP, F> {
+	/// The energy stored in a full tank, in BTU, computed at compile time.
+	pub const MAX_ENERGY_BTU: BTU = CAP * F::ENERGY_DENSITY_BTU;
+}
 
-	fn energy_density() -> Self::Output {
-		assert!(C <= 100, "C is not between 0 and 100");
+/// A store of fuel `F` that loses `discharge_per_tick` units per tick while sitting idle, like a
+/// battery would.
+pub struct SelfDischarging<F: Fuel> {
+	amount: RefCell<u32>,
+	discharge_per_tick: u32,
+	_m: PhantomData<F>,
+}
 
-        let ratio = C as f32 / 100.0;
-        let inverse_ratio = 1.0 - ratio;
+impl<F: Fuel> SelfDischarging<F> {
+	pub fn new(amount: u32, discharge_per_tick: u32) -> Self {
+		Self {
+			amount: RefCell::new(amount),
+			discharge_per_tick,
+			_m: Default::default(),
+		}
+	}
 
-        let energy_density1 = BTU::from(F1::energy_density().into()) as f32 * ratio;
-        let energy_density2 = BTU::from(F2::energy_density().into()) as f32 * inverse_ratio;
+	/// Let a tick pass, discharging the store (down to empty at most).
+	pub fn tick(&self) {
+		let mut amount = self.amount.borrow_mut();
+		*amount = amount.saturating_sub(self.discharge_per_tick);
+	}
 
-        (energy_density1 + energy_density2) as u32
+	/// Take all of the fuel that is left out of the store, leaving it empty.
+	pub fn draw(&self) -> FuelContainer<F> {
+		FuelContainer::new(self.amount.replace(0))
 	}
 }
 
-// Now, any of our existing energy providers can be used with a mix fuel.
+/// A tank that can hold up to `capacity` units of fuel `F`, and hands it out one unit at a time.
+pub struct Tank<F: Fuel> {
+	capacity: u32,
+	amount: RefCell<u32>,
+	_marker: PhantomData<F>,
+}
 
-/// A function that returns the energy produced by the `OmniGenerator` with efficiency of 80%, when
-/// the fuel type is an even a mix of `Diesel` as `LithiumBattery`;
-pub fn omni_80_energy(amount: u32) -> BTU {
-	let fuel_container: FuelContainer<Mixed<Diesel, LithiumBattery>> = FuelContainer::new(amount);
-	let og = OmniGenerator::<80>;
-	og.provide_energy(fuel_container).into()
+impl<F: Fuel> Tank<F> {
+	/// Create a tank of the given `capacity`, holding `amount` units of fuel (clamped to
+	/// `capacity`).
+	pub fn new(capacity: u32, amount: u32) -> Self {
+		Self {
+			capacity,
+			amount: RefCell::new(amount.min(capacity)),
+			_marker: Default::default(),
+		}
+	}
+
+	/// The amount of fuel left in the tank.
+	pub fn amount(&self) -> u32 {
+		*self.amount.borrow()
+	}
+
+	/// The most fuel that this tank can hold.
+	pub fn capacity(&self) -> u32 {
+		self.capacity
+	}
 }
 
-// Finally, let's consider marker traits, and some trait bounds.
+/// Collecting containers into a tank sums up their amounts. As there is no way to choose a
+/// capacity here, the resulting tank is as big as it gets, and the sum saturates at `u32::MAX`.
+impl<F: Fuel> FromIterator<FuelContainer<F>> for Tank<F> {
+	fn from_iter<I: IntoIterator<Item = FuelContainer<F>>>(iter: I) -> Self {
+		let mut tank = Tank::new(u32::MAX, 0);
+		tank.extend(iter);
+		tank
+	}
+}
 
-/// Some traits are just markers. They don't bring any additional functionality anything, other than
-/// marking a type with some trait.
-pub trait IsRenewable {}
-impl IsRenewable for LithiumBattery {}
+/// Pours the containers into the tank, until it reaches its capacity. Whatever does not fit is
+/// lost.
+impl<F: Fuel> Extend<FuelContainer<F>> for Tank<F> {
+	fn extend<I: IntoIterator<Item = FuelContainer<F>>>(&mut self, iter: I) {
+		let amount = self.amount.get_mut();
+		for container in iter {
+			*amount = amount.saturating_add(container.amount).min(self.capacity);
+		}
+	}
+}
 
-/// Define the following struct such that it only provides energy if the fuel is `IsRenewable`.
+/// An iterator draining a [`Tank`], yielding single-unit containers until the tank is empty.
+pub struct TankUnits<'a, F: Fuel> {
+	tank: &'a Tank<F>,
+}
+
+impl<F: Fuel> Iterator for TankUnits<'_, F> {
+	type Item = FuelContainer<F>;
+
+	fn next(&mut self) -> Option<Self::Item> {
+		let mut amount = self.tank.amount.borrow_mut();
+		if *amount == 0 {
+			return None
+		}
+		*amount -= 1;
+		Some(FuelContainer::new(1))
+	}
+
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		let left = self.tank.amount() as usize;
+		(left, Some(left))
+	}
+}
+
+impl<'a, F: Fuel> IntoIterator for &'a Tank<F> {
+	type Item = FuelContainer<F>;
+	type IntoIter = TankUnits<'a, F>;
+
+	fn into_iter(self) -> Self::IntoIter {
+		TankUnits { tank: self }
+	}
+}
+
+/// The energy stored in `f`, in BTU, regardless of any provider or efficiency.
 ///
-/// It has perfect efficiency.
-pub struct GreenEngine<F: Fuel + IsRenewable>(pub PhantomData<F>);
-impl<F: Fuel + IsRenewable> ProvideEnergy<F> for GreenEngine<F> {
-	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
-		//todo!("complete the implementation; no te that you might need to change the trait bounds and generics of the `impl` line");
-		self.provide_energy_ideal(f)
+/// This is the theoretical maximum that any [`ProvideEnergy`] could get out of `f`.
+pub fn stored_energy<F: Fuel>(f: &FuelContainer<F>) -> BTU {
+	BTU::from(F::energy_density().into()) * f.amount
+}
+
+/// The most energy, in BTU, that any provider could ever get out of `amount` units of `F`, at an
+/// ideal efficiency of 100%. This is the ceiling that real outputs are measured against.
+pub fn max_theoretical_output<F: Fuel>(amount: u32) -> BTU {
+	stored_energy(&FuelContainer::<F>::new(amount))
+}
+
+/// Encode `c` into 5 bytes, for sending it over the network: the [`FuelKind::tag`] of its fuel,
+/// followed by its amount in little endian.
+pub fn to_bytes<F: KnownFuel>(c: &FuelContainer<F>) -> Vec<u8> {
+	let mut bytes = vec![F::KIND.tag()];
+	bytes.extend_from_slice(&c.amount.to_le_bytes());
+	bytes
+}
+
+/// The inverse of [`to_bytes`]. As the fuel type is only known at runtime then, it is decoded into
+/// a [`FuelKind`] along with the amount.
+pub fn from_bytes(b: &[u8]) -> Result<(FuelKind, u32), DecodeError> {
+	let [tag, amount @ ..] = b else {
+		return Err(DecodeError::WrongLength)
+	};
+	let amount: [u8; 4] = amount.try_into().map_err(|_| DecodeError::WrongLength)?;
+	let kind = FuelKind::from_tag(*tag).ok_or(DecodeError::UnknownTag(*tag))?;
+	Ok((kind, u32::from_le_bytes(amount)))
+}
+
+/// The reasons for which [`from_bytes`] can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeError {
+	/// The input is not exactly 5 bytes long.
+	WrongLength,
+	/// The tag does not belong to any [`FuelKind`].
+	UnknownTag(u8),
+}
+
+/// The energy stored in a container, regardless of its fuel type.
+///
+/// Containers of different fuels are different types, but they can all be used as a
+/// `dyn ContainerEnergy`, e.g. to sum up a heterogeneous portfolio.
+pub trait ContainerEnergy {
+	/// Same as [`stored_energy`].
+	fn btu(&self) -> BTU;
+}
+
+impl<F: Fuel> ContainerEnergy for FuelContainer<F> {
+	fn btu(&self) -> BTU {
+		stored_energy(self)
 	}
 }
 
-/// Define the following struct such that it only provides energy if the fuel's output type is
-/// `BTU`.
+/// Swap the fuel in `c` for an `F2` container storing the same energy.
 ///
-/// It has perfect efficiency.
-pub struct BritishEngine<F: Fuel<Output = BTU>>(pub PhantomData<F>);
-impl<F: Fuel<Output = BTU>> ProvideEnergy<F> for BritishEngine<F> {
-	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+/// The amount of `F2` is rounded down, so up to (but excluding) one unit of `F2` worth of energy
+/// can be lost. If `F2` has no energy density at all, the returned container is empty.
+pub fn convert_energy<F1: Fuel, F2: Fuel>(c: FuelContainer<F1>) -> FuelContainer<F2> {
+	let energy_density = BTU::from(F2::energy_density().into());
+	let amount = stored_energy(&c).checked_div(energy_density).unwrap_or(0);
+	FuelContainer::new(amount)
+}
+
+/// The smallest number of units `(f2_units, f1_units)` of `F2` and `F1` that store the same energy.
+///
+/// Since the densities are whole BTU, this is always exact: e.g. `energy_parity::<Uranium,
+/// Diesel>()` is `(10, 1)`, as 10 units of Diesel store as much energy as 1 unit of Uranium. If
+/// either fuel has no energy density, no amount of it can ever match the other, and `(0, 0)` is
+/// returned.
+pub fn energy_parity<F1: Fuel, F2: Fuel>() -> (u32, u32) {
+	let energy_density1 = BTU::from(F1::energy_density().into());
+	let energy_density2 = BTU::from(F2::energy_density().into());
+	if energy_density1 == 0 || energy_density2 == 0 {
+		return (0, 0)
+	}
+
+	let divisor = gcd(energy_density1, energy_density2);
+	(energy_density1 / divisor, energy_density2 / divisor)
+}
+
+/// Whether `F1` and `F2` have the same energy density, once converted to BTU.
+pub fn same_density<F1: Fuel, F2: Fuel>() -> bool {
+	BTU::from(F1::energy_density().into()) == BTU::from(F2::energy_density().into())
+}
+
+/// The greatest common divisor of `a` and `b`.
+fn gcd(mut a: u32, mut b: u32) -> u32 {
+	while b != 0 {
+		(a, b) = (b, a % b);
+	}
+	a
+}
+
+/// The energy, in BTU, that `amount` units of `F` yield at each of `efficiencies`, e.g. for
+/// plotting how output trades off against efficiency.
+pub fn efficiency_output_curve<F: Fuel>(amount: u32, efficiencies: &[u8]) -> Vec<(u8, BTU)> {
+	efficiencies.iter().map(|&e| (e, energy_with_efficiency::<F>(amount, e))).collect()
+}
+
+/// The energy, in BTU, that `amount` units of `F` yield at an efficiency of `e`.
+///
+/// This is what [`ProvideEnergy::provide_energy_with_efficiency`] is based on, and thus saturates
+/// `e` at 100%. The result is rounded to the nearest BTU. Like there, the total energy stored in
+/// `amount` units must fit in a `BTU`.
+fn energy_with_efficiency<F: Fuel>(amount: u32, e: u8) -> BTU {
+	energy_with_density(BTU::from(F::energy_density().into()), amount, e)
+}
+
+/// Same as [`energy_with_efficiency`], for a fuel whose energy `density` is only known at runtime.
+fn energy_with_density(density: BTU, amount: u32, e: u8) -> BTU {
+	let efficiency = e.min(100) as f32 / 100.0;
+	let total_energy = density * amount;
+	(total_energy as f32 * efficiency).round() as u32
+}
+
+/// The least amount of `F` that yields at least `target` BTU at an efficiency of `e`, or `None` if
+/// no amount can.
+fn amount_for_energy<F: Fuel>(target: BTU, e: u8) -> Option<u32> {
+	if target == 0 {
+		return Some(0)
+	}
+	let per_unit = BTU::from(F::energy_density().into()) as u64 * e.min(100) as u64;
+	if per_unit == 0 {
+		return None
+	}
+
+	// This is exact, save for the rounding of `energy_with_efficiency`, which can only spare us
+	// some fuel.
+	let mut amount = u32::try_from((target as u64 * 100 + per_unit - 1) / per_unit).ok()?;
+	while amount > 0 && energy_with_efficiency::<F>(amount - 1, e) >= target {
+		amount -= 1;
+	}
+	Some(amount)
+}
+
+/// The running total of the energy, in BTU, produced by a consumption `log` of `(tick, amount)`
+/// pairs, where each amount of `F` is consumed at the given `efficiency`.
+///
+/// The totals saturate at `BTU::MAX` rather than overflowing, even where a single entry alone
+/// would not fit in a `BTU`.
+pub fn cumulative_energy<F: Fuel>(log: &[(u32, u32)], efficiency: u8) -> Vec<(u32, BTU)> {
+	let density = BTU::from(F::energy_density().into()) as u64;
+	let efficiency = efficiency.min(100) as f64 / 100.0;
+	let mut total: BTU = 0;
+	log.iter()
+		.map(|&(tick, amount)| {
+			let energy = (density * amount as u64) as f64 * efficiency;
+			total = total.saturating_add(energy.round().min(BTU::MAX as f64) as BTU);
+			(tick, total)
+		})
+		.collect()
+}
+
+/// The specs of a [`ProvideEnergy`], as returned by [`ProvideEnergy::spec_sheet`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProviderSpec {
+	pub nominal_efficiency: u8,
+	pub stateful: bool,
+	pub accepted_fuels: Vec<&'static str>,
+}
+
+/// Something that can provide energy from a given `F` fuel type, like a power-plant.
+pub trait ProvideEnergy<F: Fuel> {
+	/// Consume the fuel container and return the created energy, based on the power density of the
+	/// fuel and potentially other factors.
+	///
+	/// Some fuel providers might have some kind of decay or inefficiency, which should be reflected
+	/// here. Otherwise, [ProvideEnergy::provide_energy_with_efficiency] or
+	/// [ProvideEnergy::provide_energy_ideal] might be good enough.
+	///
+	/// Not all `ProvideEnergy` implementations need to have internal state. Therefore, this
+	/// interface accepts `&self`, not `&mut self`. You might need to use special language features
+	/// to overcome this.
+	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output;
+
+	/// Convert the amount of fuel in `f` with an exact efficiency of `e`.
+	///
+	/// NOTE: all efficiencies are interpreted as u8 values that can be at most 100, and represent a
+	/// percent. If an efficiency above 100 is supplied, the code should treat it as 100. That is to
+	/// say that the efficiency is "saturating" at 100%.
+	///
+	/// This method must be provided as it will be the same in all implementations.
+	fn provide_energy_with_efficiency(&self, f: FuelContainer<F>, e: u8) -> <F as Fuel>::Output {
+		// Convert back to the fuel's output unit
+		<F as Fuel>::Output::from(energy_with_efficiency::<F>(f.amount, e))
+	}
+
+	/// Same as [`ProvideEnergy::provide_energy_with_efficiency`], but with an exact efficiency of
+	/// `num / den`, e.g. for efficiencies like 1/3 that a percent cannot represent.
+	///
+	/// The energy is computed in integers, and rounded down. Like percents, the efficiency
+	/// saturates at 100%, and a `den` of zero yields no energy at all.
+	fn provide_energy_with_fraction(
+		&self,
+		f: FuelContainer<F>,
+		num: u32,
+		den: u32,
+	) -> <F as Fuel>::Output {
+		if den == 0 {
+			return <F as Fuel>::Output::from(0)
+		}
+
+		let density = BTU::from(F::energy_density().into()) as u64;
+		let energy = density * f.amount as u64 * num.min(den) as u64 / den as u64;
+		<F as Fuel>::Output::from(energy.min(BTU::MAX as u64) as BTU)
+	}
+
+	/// Same as [`ProvideEnergy::provide_energy_with_efficiency`], but with an efficiency of 100.
+	///
+	/// This method must be provided as it will be the same in all implementations.
+	fn provide_energy_ideal(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+		self.provide_energy_with_efficiency(f, 100) 
+	}
+
+	/// The efficiency that this provider is rated at, e.g. for displaying its specs without
+	/// running any fuel through it. By default, this is a perfect 100.
+	fn nominal_efficiency(&self) -> u8 {
+		100
+	}
+
+	/// Whether this provider has internal state, such that its output may change from one call to
+	/// the next. By default, providers are stateless.
+	fn is_stateful(&self) -> bool {
+		false
+	}
+
+	/// The names of the fuels that this provider accepts, as given by [`Fuel::name`]. By default,
+	/// this is only `F`, but providers that accept any fuel may declare so.
+	fn accepted_fuels(&self) -> Vec<&'static str> {
+		vec![F::name()]
+	}
+
+	/// All of the specs of this provider, e.g. for displaying it in a catalog.
+	fn spec_sheet(&self) -> ProviderSpec {
+		ProviderSpec {
+			nominal_efficiency: self.nominal_efficiency(),
+			stateful: self.is_stateful(),
+			accepted_fuels: self.accepted_fuels(),
+		}
+	}
+
+	/// Convert the amount of fuel in `f`, where the fuel is only of the given `quality`.
+	///
+	/// `quality` is a percent, saturating at 100 like efficiencies, and degrades the
+	/// [`ProvideEnergy::nominal_efficiency`] proportionally, i.e. the effective efficiency is
+	/// `nominal_efficiency * quality / 100`.
+	fn provide_energy_quality(&self, f: FuelContainer<F>, quality: u8) -> <F as Fuel>::Output {
+		let base = self.nominal_efficiency().min(100) as u32;
+		let efficiency = base * quality.min(100) as u32 / 100;
+		self.provide_energy_with_efficiency(f, efficiency as u8)
+	}
+
+	/// The extra energy, in BTU, that a single unit of fuel on top of `current_amount` yields. As
+	/// no container holds more than `u32::MAX` units, there is no extra energy beyond that.
+	///
+	/// This calls [`ProvideEnergy::provide_energy`] twice, so a stateful provider advances its
+	/// state twice as well. For such providers, this is what exposes their non-linearity.
+	fn marginal_energy(&self, current_amount: u32) -> BTU {
+		let current: BTU = self.provide_energy(FuelContainer::new(current_amount)).into();
+		let next_amount = current_amount.saturating_add(1);
+		let next: BTU = self.provide_energy(FuelContainer::new(next_amount)).into();
+		next.saturating_sub(current)
+	}
+
+	/// Same as [`ProvideEnergy::provide_energy`], but adds the produced energy, in BTU, to the
+	/// caller's `running` total (saturating at `BTU::MAX`) instead of returning it.
+	fn provide_energy_accumulate(&self, f: FuelContainer<F>, running: &mut BTU) {
+		let energy: BTU = self.provide_energy(f).into();
+		*running = running.saturating_add(energy);
+	}
+
+	/// Draw `units` out of `tank` (or all of it, if it holds fewer) and run them through this
+	/// provider, returning the produced energy along with what is left of the tank.
+	fn consume(
+		&self,
+		tank: FuelContainer<F>,
+		units: u32,
+	) -> (<F as Fuel>::Output, FuelContainer<F>) {
+		let drawn = units.min(tank.amount);
+		let energy = self.provide_energy(FuelContainer::new(drawn));
+		(energy, FuelContainer::new(tank.amount - drawn))
+	}
+
+	/// Run `f` through this provider `passes` times, where the energy produced by each pass is
+	/// turned back into fuel (rounding down to whole units) and fed into the next one.
+	///
+	/// Losses thus compound multiplicatively: two passes at 50% yield about 25% of the ideal
+	/// energy. Zero passes return the energy stored in `f` untouched.
+	fn compound_pass(&self, f: FuelContainer<F>, passes: u32) -> BTU {
+		let density: BTU = F::energy_density().into();
+		let mut energy = stored_energy(&f);
+		for _ in 0..passes {
+			if density == 0 {
+				return 0
+			}
+			energy = self.provide_energy(FuelContainer::new(energy / density)).into();
+		}
+		energy
+	}
+}
+
+/// A nuclear reactor that can only consume `Uranium` and provide energy with 99% efficiency.
+#[derive(Default, Clone, Copy)]
+pub struct NuclearReactor;
+impl ProvideEnergy<Uranium> for NuclearReactor {
+	fn provide_energy(&self, f: FuelContainer<Uranium>) -> <Uranium as Fuel>::Output {
 		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
-		self.provide_energy_ideal(f)
+		let efficiency = 99; // 99% efficiency for the nuclear reactor
+        self.provide_energy_with_efficiency(f, efficiency)
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		99
+	}
+}
+
+/// A nuclear reactor that needs to warm up: for the first `WARMUP` calls to `provide_energy`, it
+/// only has an efficiency of 50%. After that, it reaches the 99% of a [`NuclearReactor`].
+pub struct Reactor<const WARMUP: u32> {
+	calls: RefCell<u32>,
+}
+
+impl<const WARMUP: u32> Reactor<WARMUP> {
+	pub fn new() -> Self {
+		Reactor { calls: RefCell::new(0) }
+	}
+}
+
+impl<const WARMUP: u32> Default for Reactor<WARMUP> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<const WARMUP: u32> ProvideEnergy<Uranium> for Reactor<WARMUP> {
+	fn provide_energy(&self, f: FuelContainer<Uranium>) -> <Uranium as Fuel>::Output {
+		let mut calls = self.calls.borrow_mut();
+		let efficiency = if *calls < WARMUP { 50 } else { 99 };
+		*calls = calls.saturating_add(1);
+
+		self.provide_energy_with_efficiency(f, efficiency)
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		99
+	}
+
+	fn is_stateful(&self) -> bool {
+		true
+	}
+}
+
+/// A combustion engine that can only consume `Diesel`.
+///
+/// The `DECAY` const must be interpreted as such: per every `DECAY` times `provide_energy` is
+/// called on an instance of this type, the efficiency should reduce by one. The initial efficiency
+/// must be configurable with a `fn new(efficiency: u8) -> Self`.
+pub struct InternalCombustion<const DECAY: u32>{/* Fill the fields as needed */
+	efficiency: RefCell<u8>,
+    call_count: RefCell<u32>,
+	initial_efficiency: u8,
+}
+
+impl<const DECAY: u32> InternalCombustion<DECAY> {
+	pub fn new(efficiency: u8) -> Self {
+		InternalCombustion {
+            efficiency: RefCell::new(efficiency.min(100)),
+            call_count: RefCell::new(0),
+			initial_efficiency: efficiency.min(100),
+        }
+	}
+
+	/// Whether this engine still operates within its nominal efficiency, that is, whether it has
+	/// not decayed below 80% of the efficiency it started with.
+	pub fn is_healthy(&self) -> bool {
+		*self.efficiency.borrow() as u32 * 100 >= self.initial_efficiency as u32 * 80
+	}
+
+	/// The efficiency of the call with index `count`, given the efficiency of the call before it.
+	fn decayed(count: u32, efficiency: u8) -> u8 {
+		if count % DECAY == 0 && efficiency > 1 && count >= DECAY {
+			efficiency - 1
+		} else {
+			efficiency
+		}
+	}
+
+	/// The efficiencies that the next `calls` calls to `provide_energy` would have, without
+	/// actually making them.
+	fn projected_efficiencies(&self, calls: u32) -> impl Iterator<Item = u8> {
+		let mut count = *self.call_count.borrow();
+		let mut efficiency = *self.efficiency.borrow();
+		(0..calls).map(move |_| {
+			efficiency = Self::decayed(count, efficiency);
+			count += 1;
+			efficiency
+		})
+	}
+
+	/// The total amount of fuel needed to deliver at least `target_btu_per_tick` in each of the
+	/// next `ticks` calls, taking into account that the engine keeps decaying, and thus needs more
+	/// fuel per tick as time goes on.
+	///
+	/// Saturates at `u32::MAX`, which is also what is returned if the engine has decayed to an
+	/// efficiency of zero and can therefore never deliver any energy.
+	pub fn fuel_for_ticks(&self, ticks: u32, target_btu_per_tick: BTU) -> u32 {
+		self.projected_efficiencies(ticks).fold(0u32, |total, efficiency| {
+			let amount = amount_for_energy::<Diesel>(target_btu_per_tick, efficiency);
+			total.saturating_add(amount.unwrap_or(u32::MAX))
+		})
+	}
+
+	/// The number of calls, each burning `amount_per_call` units, after which the energy lost to
+	/// decay, compared to a freshly serviced engine running at its initial efficiency, exceeds
+	/// `maintenance_cost_btu`. That is when servicing the engine becomes worth it.
+	///
+	/// This only projects the calls, without making them. Returns `u32::MAX` if the loss never
+	/// exceeds the cost.
+	pub fn maintenance_break_even(&self, maintenance_cost_btu: BTU, amount_per_call: u32) -> u32 {
+		let cost = maintenance_cost_btu as u64;
+		let serviced = energy_with_efficiency::<Diesel>(amount_per_call, self.initial_efficiency);
+		let mut loss: u64 = 0;
+		let mut calls: u64 = 0;
+		let mut count = *self.call_count.borrow() as u64;
+		let mut efficiency = *self.efficiency.borrow();
+
+		// the efficiency only changes every `DECAY` calls, so the loss is projected one decay step
+		// at a time, rather than one call at a time.
+		loop {
+			efficiency = Self::decayed(count as u32, efficiency);
+			let lost = serviced.saturating_sub(energy_with_efficiency::<Diesel>(
+				amount_per_call,
+				efficiency,
+			)) as u64;
+			// once the engine cannot decay any further, this step lasts forever.
+			let step = (efficiency > 1).then(|| DECAY as u64 - count % DECAY as u64);
+
+			if let Some(remaining) = (cost - loss).checked_div(lost).map(|n| n + 1) {
+				if step.map_or(true, |step| remaining <= step) {
+					return (calls + remaining).min(u32::MAX as u64) as u32
+				}
+			}
+			let Some(step) = step else { return u32::MAX };
+
+			loss += lost * step;
+			calls += step;
+			count += step;
+			if calls >= u32::MAX as u64 {
+				return u32::MAX
+			}
+		}
+	}
+
+	/// The efficiency at which an [`OmniGenerator`] would produce as much energy from `amount`
+	/// units of `Diesel` as the next call to this engine will, taking its decay into account.
+	///
+	/// As output is linear in efficiency, this is the closest match after rounding. Ties go to the
+	/// lower efficiency. This only projects the call, without making it.
+	pub fn crossover_efficiency(&self, amount: u32) -> u8 {
+		let next = self.projected_efficiencies(1).next().unwrap_or_default();
+		let output = energy_with_efficiency::<Diesel>(amount, next);
+		(0..=100)
+			.min_by_key(|&e| energy_with_efficiency::<Diesel>(amount, e).abs_diff(output))
+			.unwrap_or_default()
+	}
+
+	/// The (population) variance of the outputs, in BTU, of the next `calls` calls, each burning
+	/// `amount_per_call` units, rounded down. This quantifies how much the output drifts due to
+	/// decay.
+	///
+	/// This only projects the calls, without making them. No calls have no variance.
+	pub fn output_variance(&self, amount_per_call: u32, calls: u32) -> u64 {
+		if calls == 0 {
+			return 0
+		}
+
+		let (sum, sum_of_squares) = self.projected_efficiencies(calls).fold(
+			(0u128, 0u128),
+			|(sum, sum_of_squares), efficiency| {
+				let energy = energy_with_efficiency::<Diesel>(amount_per_call, efficiency) as u128;
+				(sum + energy, sum_of_squares + energy * energy)
+			},
+		);
+		let n = calls as u128;
+		((n * sum_of_squares - sum * sum) / (n * n)) as u64
+	}
+
+	/// Same as [`ProvideEnergy::provide_energy`], but the efficiency of this call is given by
+	/// `eff_fn` of the index of this call, rather than by the decayed efficiency.
+	///
+	/// The call is still counted, so it affects when the next decay happens.
+	pub fn provide_energy_fn(
+		&self,
+		f: FuelContainer<Diesel>,
+		eff_fn: impl Fn(u32) -> u8,
+	) -> <Diesel as Fuel>::Output {
+		let mut current_count = self.call_count.borrow_mut();
+		let efficiency = eff_fn(*current_count);
+		*current_count += 1;
+
+		self.provide_energy_with_efficiency(f, efficiency)
+	}
+}
+
+impl<const DECAY: u32> ProvideEnergy<Diesel> for InternalCombustion<DECAY> {
+	fn provide_energy(&self, f: FuelContainer<Diesel>) -> <Diesel as Fuel>::Output {
+		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
+		let mut current_count = self.call_count.borrow_mut();
+		let mut current_efficiency = self.efficiency.borrow_mut();
+		//println!("{}", *current_efficiency);
+
+		*current_efficiency = Self::decayed(*current_count, *current_efficiency);
+		*current_count += 1;
+        
+        self.provide_energy_with_efficiency(f, *current_efficiency)
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		self.initial_efficiency
+	}
+
+	fn is_stateful(&self) -> bool {
+		true
+	}
+}
+
+/// The opposite of [`InternalCombustion`]: an engine that can only consume `Diesel`, and which
+/// warms up as it is used.
+///
+/// Per every `RAMP` times `provide_energy` is called, the efficiency increases by one, until it
+/// reaches `target`, after which it holds steady. A `RAMP` of zero means no warm-up at all.
+pub struct WarmupEngine<const RAMP: u32> {
+	efficiency: RefCell<u8>,
+	target: u8,
+	calls: RefCell<u32>,
+}
+
+impl<const RAMP: u32> WarmupEngine<RAMP> {
+	pub fn new(efficiency: u8, target: u8) -> Self {
+		WarmupEngine {
+			efficiency: RefCell::new(efficiency.min(100)),
+			target: target.min(100),
+			calls: RefCell::new(0),
+		}
+	}
+}
+
+impl<const RAMP: u32> ProvideEnergy<Diesel> for WarmupEngine<RAMP> {
+	fn provide_energy(&self, f: FuelContainer<Diesel>) -> <Diesel as Fuel>::Output {
+		let mut calls = self.calls.borrow_mut();
+		let mut efficiency = self.efficiency.borrow_mut();
+
+		if RAMP > 0 && *calls >= RAMP && *calls % RAMP == 0 && *efficiency < self.target {
+			*efficiency += 1;
+		}
+		*calls += 1;
+
+		self.provide_energy_with_efficiency(f, *efficiency)
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		self.target
+	}
+
+	fn is_stateful(&self) -> bool {
+		true
+	}
+}
+
+/// The settings of a [`ConfigurableEngine`], chosen at runtime rather than through const generics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProviderConfig {
+	efficiency: u8,
+	floor: u8,
+	decay_interval: u32,
+}
+
+impl ProviderConfig {
+	/// An engine that starts at `efficiency`, and loses one percent of it per every
+	/// `decay_interval` calls, but never drops below `floor`.
+	///
+	/// Both efficiencies saturate at 100, and a `decay_interval` of zero means no decay at all.
+	pub fn new(efficiency: u8, floor: u8, decay_interval: u32) -> Self {
+		Self { efficiency: efficiency.min(100), floor: floor.min(100), decay_interval }
+	}
+
+	/// The sensible defaults of an engine, which is the single source of truth for all of them:
+	/// starting at full efficiency, and losing one percent per 10 calls, down to 50%.
+	pub fn from_defaults() -> Self {
+		Self::new(100, 50, 10)
+	}
+}
+
+/// A unique type explicitly representing a [`ProviderConfigBuilder`] whose efficiency is not set.
+pub struct NoEfficiency;
+/// A unique type explicitly representing a [`ProviderConfigBuilder`] whose efficiency is set.
+pub struct WithEfficiency {
+	efficiency: u8,
+}
+
+/// A builder for [`ProviderConfig`]s, using the "type-state" pattern to require the efficiency.
+/// The floor and decay interval are optional, and otherwise taken from
+/// [`ProviderConfig::from_defaults`].
+///
+/// ## Example
+///
+/// ```
+/// use pba_qualifier_exam::h_advanced_traits::{ProviderConfig, ProviderConfigBuilder};
+///
+/// # fn main() {
+/// let config = ProviderConfigBuilder::new().decay_interval(5).efficiency(90).build();
+/// assert_eq!(config, ProviderConfig::new(90, 50, 5));
+/// # }
+/// ```
+///
+/// This code will simply fail to compile:
+///
+/// ```compile_fail
+/// use pba_qualifier_exam::h_advanced_traits::ProviderConfigBuilder;
+///
+/// # fn main() {
+/// let config = ProviderConfigBuilder::new().floor(10).build();
+/// # }
+/// ```
+pub struct ProviderConfigBuilder<E> {
+	efficiency: E,
+	floor: u8,
+	decay_interval: u32,
+}
+
+impl ProviderConfigBuilder<NoEfficiency> {
+	pub fn new() -> Self {
+		let defaults = ProviderConfig::from_defaults();
+		Self {
+			efficiency: NoEfficiency,
+			floor: defaults.floor,
+			decay_interval: defaults.decay_interval,
+		}
+	}
+
+	pub fn efficiency(self, efficiency: u8) -> ProviderConfigBuilder<WithEfficiency> {
+		ProviderConfigBuilder {
+			efficiency: WithEfficiency { efficiency },
+			floor: self.floor,
+			decay_interval: self.decay_interval,
+		}
+	}
+}
+
+impl Default for ProviderConfigBuilder<NoEfficiency> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+
+impl<E> ProviderConfigBuilder<E> {
+	pub fn floor(mut self, floor: u8) -> Self {
+		self.floor = floor;
+		self
+	}
+
+	pub fn decay_interval(mut self, decay_interval: u32) -> Self {
+		self.decay_interval = decay_interval;
+		self
+	}
+}
+
+impl ProviderConfigBuilder<WithEfficiency> {
+	pub fn build(self) -> ProviderConfig {
+		ProviderConfig::new(self.efficiency.efficiency, self.floor, self.decay_interval)
+	}
+}
+
+/// Like an [`InternalCombustion`] that can only consume `Diesel`, but with its behavior read from
+/// a [`ProviderConfig`], so that many configurations can share one type.
+pub struct ConfigurableEngine {
+	config: ProviderConfig,
+	efficiency: RefCell<u8>,
+	calls: RefCell<u32>,
+}
+
+impl ConfigurableEngine {
+	pub fn new(config: ProviderConfig) -> Self {
+		ConfigurableEngine {
+			config,
+			efficiency: RefCell::new(config.efficiency),
+			calls: RefCell::new(0),
+		}
+	}
+
+	/// The configuration this engine was created with.
+	pub fn config(&self) -> ProviderConfig {
+		self.config
+	}
+}
+
+impl ProvideEnergy<Diesel> for ConfigurableEngine {
+	fn provide_energy(&self, f: FuelContainer<Diesel>) -> <Diesel as Fuel>::Output {
+		let mut calls = self.calls.borrow_mut();
+		let mut efficiency = self.efficiency.borrow_mut();
+
+		let interval = self.config.decay_interval;
+		if interval > 0 && *calls >= interval && *calls % interval == 0 {
+			*efficiency = efficiency.saturating_sub(1).max(self.config.floor.min(*efficiency));
+		}
+		*calls = calls.saturating_add(1);
+
+		self.provide_energy_with_efficiency(f, *efficiency)
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		self.config.efficiency
+	}
+
+	fn is_stateful(&self) -> bool {
+		true
+	}
+}
+
+/// A flex generator that can be reconfigured to run on a different [`FuelKind`] at any time, at
+/// a fixed efficiency.
+///
+/// As the fuel is only known at runtime, this cannot implement [`ProvideEnergy`] for any fuel
+/// type. Instead, it looks up the density of the current fuel on every call to
+/// [`SwitchableGenerator::produce`].
+pub struct SwitchableGenerator {
+	fuel: RefCell<FuelKind>,
+	efficiency: u8,
+}
+
+impl SwitchableGenerator {
+	/// Like all efficiencies, `efficiency` saturates at 100.
+	pub fn new(fuel: FuelKind, efficiency: u8) -> Self {
+		Self { fuel: RefCell::new(fuel), efficiency: efficiency.min(100) }
+	}
+
+	/// Switch over to fuel `k`, from the next call to [`SwitchableGenerator::produce`] on.
+	pub fn set_fuel(&self, k: FuelKind) {
+		*self.fuel.borrow_mut() = k;
+	}
+
+	/// The energy, in BTU, produced from `amount` units of the currently selected fuel.
+	pub fn produce(&self, amount: u32) -> BTU {
+		let density = self.fuel.borrow().energy_density_btu();
+		energy_with_density(density, amount, self.efficiency)
+	}
+}
+
+/// A hypothetical device that can, unlike the `InternalCombustion`, consume **any fuel** that's of
+/// type `trait Fuel`. It can provide a fixed efficiency regardless of fuel type. As before,
+/// EFFICIENCY is a u8 whose value should not exceed 100, is interpreted as a percent, and should
+/// saturate at 100% when a higher value is supplied.
+#[derive(Default, Clone, Copy)]
+pub struct OmniGenerator<const EFFICIENCY: u8>;
+
+// NOTE: implement `ProvideEnergy` for `OmniGenerator` using only one `impl` block.
+impl<const EFFICIENCY: u8, F: Fuel> ProvideEnergy<F> for OmniGenerator<EFFICIENCY> {
+	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
+		self.provide_energy_with_efficiency(f, EFFICIENCY)
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		EFFICIENCY.min(100)
+	}
+
+	fn accepted_fuels(&self) -> Vec<&'static str> {
+		vec!["Any"]
+	}
+}
+
+/// A source that delivers a fixed `BTU_OUT` no matter how much fuel it is fed, as long as it is fed
+/// anything at all. Like the `OmniGenerator`, it can consume any fuel.
+///
+/// The output is converted to the unit of the fuel, so `BTU_OUT` must fit in that unit: at most
+/// `u32::MAX / 1055 = 4_071_059` BTU for fuels measured in [`Joule`], such as `Diesel`, and at
+/// most `u32::MAX / 251 = 17_111_423` BTU for fuels measured in [`Calorie`]. Larger outputs
+/// overflow just like the conversions themselves.
+#[derive(Default, Clone, Copy)]
+pub struct FixedRateSource<const BTU_OUT: u32>;
+
+impl<const BTU_OUT: u32, F: Fuel> ProvideEnergy<F> for FixedRateSource<BTU_OUT> {
+	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+		let energy = if f.amount == 0 { 0 } else { BTU_OUT };
+		<F as Fuel>::Output::from(energy)
+	}
+
+	fn accepted_fuels(&self) -> Vec<&'static str> {
+		vec!["Any"]
+	}
+}
+
+/// A provider, which can consume any fuel, whose efficiency depends on its load: it is
+/// interpolated linearly from `low_load_eff` when fed nothing, to `high_load_eff` when fed
+/// `rated_amount` units or more.
+pub struct LoadCurveProvider {
+	low_load_eff: u8,
+	high_load_eff: u8,
+	rated_amount: u32,
+}
+
+impl LoadCurveProvider {
+	/// Both efficiencies saturate at 100.
+	pub fn new(low_load_eff: u8, high_load_eff: u8, rated_amount: u32) -> Self {
+		Self {
+			low_load_eff: low_load_eff.min(100),
+			high_load_eff: high_load_eff.min(100),
+			rated_amount,
+		}
+	}
+
+	/// The efficiency when fed `amount` units of fuel, rounded towards `low_load_eff`.
+	pub fn efficiency_at(&self, amount: u32) -> u8 {
+		if self.rated_amount == 0 {
+			return self.high_load_eff
+		}
+
+		let (low, high) = (self.low_load_eff as i64, self.high_load_eff as i64);
+		let load = amount.min(self.rated_amount) as i64;
+		(low + (high - low) * load / self.rated_amount as i64) as u8
+	}
+}
+
+impl<F: Fuel> ProvideEnergy<F> for LoadCurveProvider {
+	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+		let efficiency = self.efficiency_at(f.amount);
+		self.provide_energy_with_efficiency(f, efficiency)
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		self.high_load_eff
+	}
+
+	fn accepted_fuels(&self) -> Vec<&'static str> {
+		vec!["Any"]
+	}
+}
+
+/// A wrapper around another provider, which clamps the output of each individual call to
+/// `max_per_call` BTU. There is no limit on the number of calls.
+pub struct RateLimited<P> {
+	inner: P,
+	max_per_call: BTU,
+}
+
+impl<P> RateLimited<P> {
+	pub fn new(inner: P, max_per_call: BTU) -> Self {
+		Self { inner, max_per_call }
+	}
+}
+
+impl<F: Fuel, P: ProvideEnergy<F>> ProvideEnergy<F> for RateLimited<P> {
+	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+		let energy: BTU = self.inner.provide_energy(f).into();
+		<F as Fuel>::Output::from(energy.min(self.max_per_call))
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		self.inner.nominal_efficiency()
+	}
+
+	fn is_stateful(&self) -> bool {
+		self.inner.is_stateful()
+	}
+
+	fn accepted_fuels(&self) -> Vec<&'static str> {
+		self.inner.accepted_fuels()
+	}
+}
+
+/// A wrapper around another provider, e.g. a solar plant, whose output varies by month: it is
+/// scaled by the percentage in `monthly_pct` of the current month, as set by
+/// [`Seasonal::set_month`].
+pub struct Seasonal<P> {
+	inner: P,
+	monthly_pct: [u8; 12],
+	month: RefCell<u8>,
+}
+
+impl<P> Seasonal<P> {
+	/// Wrap `inner`, starting in the first month.
+	pub fn new(inner: P, monthly_pct: [u8; 12]) -> Self {
+		Self { inner, monthly_pct, month: RefCell::new(0) }
+	}
+
+	/// Move on to month `m`, counting from 0 for January up to 11 for December.
+	///
+	/// Any other `m` is rejected, and the current month is left as it was.
+	pub fn set_month(&self, m: u8) -> Result<(), InvalidMonth> {
+		if m >= 12 {
+			return Err(InvalidMonth(m))
+		}
+		*self.month.borrow_mut() = m;
+		Ok(())
+	}
+}
+
+/// The error returned by [`Seasonal::set_month`] for a month that is not between 0 and 11.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidMonth(pub u8);
+
+impl<F: Fuel, P: ProvideEnergy<F>> ProvideEnergy<F> for Seasonal<P> {
+	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+		let energy: BTU = self.inner.provide_energy(f).into();
+		let pct = self.monthly_pct[*self.month.borrow() as usize];
+		let scaled = energy as u64 * pct as u64 / 100;
+		<F as Fuel>::Output::from(scaled.min(BTU::MAX as u64) as BTU)
+	}
+
+	fn nominal_efficiency(&self) -> u8 {
+		self.inner.nominal_efficiency()
+	}
+
+	fn is_stateful(&self) -> bool {
+		true
+	}
+
+	fn accepted_fuels(&self) -> Vec<&'static str> {
+		self.inner.accepted_fuels()
+	}
+}
+
+/// A type that can wrap two different fuel types and mix them together.
+///
+/// The energy density of the new fuel type is the average of the two given, once converted to BTU.
+/// The output unit should also be BTU.
+///
+/// This can represent a new fuel type, thus it must implement `Fuel`.
+pub struct Mixed<F1: Fuel, F2: Fuel>(PhantomData<(F1, F2)>);
+
+impl<F1: Fuel, F2: Fuel> Fuel for Mixed<F1, F2> {
+	type Output = BTU;
+
+	fn energy_density() -> Self::Output {
+		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
+		let energy_density1 = BTU::from(F1::energy_density().into());
+        let energy_density2 = BTU::from(F2::energy_density().into());
+
+        (energy_density1 + energy_density2) / 2
+	}
+
+	fn co2_per_unit() -> u32 {
+		(F1::co2_per_unit() + F2::co2_per_unit()) / 2
+	}
+
+	fn cost_per_unit() -> u32 {
+		(F1::cost_per_unit() + F2::cost_per_unit()) / 2
+	}
+}
+
+// Now think about how you can make the mixer configurable, such that it would produce a new fuel
+// with an energy density that is more influences by one type than the other.
+//
+// For example, you have a mixer of F1, F2, and some coefficient C1, where the energy density of the
+// mixture is `F1 * C1 + F2 * (1 - C1) )` where `C1` is a ratio (which you have to represent again
+// with a u8 percent).
+//
+// The main trick is to overcome the fact that `fn energy_density` does not take in a `self`, so the
+// coefficients need to be incorporated in some other way (you've already seen examples of that in
+// this file ;)).
+pub struct CustomMixed<const C: u8, F1, F2>(PhantomData<(F1, F2)>);
+impl<const C: u8, F1: Fuel, F2: Fuel> Fuel for CustomMixed<C, F1, F2> {
+	type Output = BTU;
+
+	fn energy_density() -> Self::Output {
+		assert!(C <= 100, "C is not between 0 and 100");
+		custom_mixed_density::<F1, F2>(C)
+	}
+
+	fn co2_per_unit() -> u32 {
+		assert!(C <= 100, "C is not between 0 and 100");
+		let co2_1 = F1::co2_per_unit() as u64 * C as u64;
+		let co2_2 = F2::co2_per_unit() as u64 * (100 - C) as u64;
+		((co2_1 + co2_2) / 100) as u32
+	}
+
+	fn cost_per_unit() -> u32 {
+		assert!(C <= 100, "C is not between 0 and 100");
+		custom_mixed_cost::<F1, F2>(C)
+	}
+}
+
+/// The energy density of [`CustomMixed`] with a coefficient of `c`, which must be at most 100.
+fn custom_mixed_density<F1: Fuel, F2: Fuel>(c: u8) -> BTU {
+	let ratio = c as f32 / 100.0;
+	let inverse_ratio = 1.0 - ratio;
+
+	let energy_density1 = BTU::from(F1::energy_density().into()) as f32 * ratio;
+	let energy_density2 = BTU::from(F2::energy_density().into()) as f32 * inverse_ratio;
+
+	(energy_density1 + energy_density2) as u32
+}
+
+/// A breakdown of what [`CustomMixed`] with a coefficient of `c` is made of, such as
+/// `"45% Diesel (100 BTU) + 55% LithiumBattery (200 BTU) = 155 BTU"`.
+///
+/// Like efficiencies, `c` saturates at 100.
+pub fn describe_blend<F1: Fuel, F2: Fuel>(c: u8) -> String {
+	let c = c.min(100);
+	format!(
+		"{}% {} ({} BTU) + {}% {} ({} BTU) = {} BTU",
+		c,
+		F1::name(),
+		BTU::from(F1::energy_density().into()),
+		100 - c,
+		F2::name(),
+		BTU::from(F2::energy_density().into()),
+		custom_mixed_density::<F1, F2>(c),
+	)
+}
+
+/// The cost per unit of [`CustomMixed`] with a coefficient of `c`, which must be at most 100.
+fn custom_mixed_cost<F1: Fuel, F2: Fuel>(c: u8) -> u32 {
+	(custom_mixed_cost_percent::<F1, F2>(c) / 100) as u32
+}
+
+/// The cost of 100 units of [`CustomMixed`] with a coefficient of `c`, which is exact, unlike the
+/// rounded [`custom_mixed_cost`].
+fn custom_mixed_cost_percent<F1: Fuel, F2: Fuel>(c: u8) -> u64 {
+	F1::cost_per_unit() as u64 * c as u64 + F2::cost_per_unit() as u64 * (100 - c) as u64
+}
+
+/// The inverse of [`CustomMixed`]: the coefficient `C` for which `CustomMixed<C, F1, F2>` has the
+/// energy density closest to `target`.
+///
+/// Returns `None` if `target` is not between the densities of the two fuels, as no blend of them
+/// could ever reach it.
+pub fn ratio_for_density<F1: Fuel, F2: Fuel>(target: BTU) -> Option<u8> {
+	let energy_density1 = BTU::from(F1::energy_density().into());
+	let energy_density2 = BTU::from(F2::energy_density().into());
+	let (low, high) = (energy_density1.min(energy_density2), energy_density1.max(energy_density2));
+	if target < low || target > high {
+		return None
+	}
+
+	(0..=100).min_by_key(|&c| custom_mixed_density::<F1, F2>(c).abs_diff(target))
+}
+
+/// Like [`ratio_for_density`], but among all coefficients that get equally close to `target`,
+/// picks the one that makes the blend cheapest. Returns the coefficient along with the cost per
+/// unit of the blend.
+pub fn cheapest_blend<F1: Fuel, F2: Fuel>(target: BTU) -> Option<(u8, u32)> {
+	ratio_for_density::<F1, F2>(target)?;
+
+	let c = (0..=100).min_by_key(|&c| {
+		let distance = custom_mixed_density::<F1, F2>(c).abs_diff(target);
+		(distance, custom_mixed_cost_percent::<F1, F2>(c))
+	})?;
+	Some((c, custom_mixed_cost::<F1, F2>(c)))
+}
+
+/// A fuel that behaves like `F`, except that its energy density is capped at `MAX` BTU.
+///
+/// This is a safety limit against absurd densities of untrusted fuel definitions.
+pub struct Capped<const MAX: u32, F: Fuel>(PhantomData<F>);
+impl<const MAX: u32, F: Fuel> Fuel for Capped<MAX, F> {
+	type Output = BTU;
+
+	fn energy_density() -> Self::Output {
+		BTU::from(F::energy_density().into()).min(MAX)
+	}
+
+	fn co2_per_unit() -> u32 {
+		F::co2_per_unit()
+	}
+
+	fn cost_per_unit() -> u32 {
+		F::cost_per_unit()
+	}
+}
+
+/// Like [`Mixed`], an even mix of two fuel types, but for blends that flow through in series, where
+/// the energy density is the harmonic rather than the arithmetic mean of the two, once converted to
+/// BTU. The output unit is BTU.
+///
+/// If either of the fuels holds no energy, neither does the mix.
+pub struct HarmonicMixed<F1: Fuel, F2: Fuel>(PhantomData<(F1, F2)>);
+impl<F1: Fuel, F2: Fuel> Fuel for HarmonicMixed<F1, F2> {
+	type Output = BTU;
+
+	fn energy_density() -> Self::Output {
+		let energy_density1 = BTU::from(F1::energy_density().into()) as u64;
+		let energy_density2 = BTU::from(F2::energy_density().into()) as u64;
+		if energy_density1 == 0 || energy_density2 == 0 {
+			return 0
+		}
+
+		(2 * energy_density1 * energy_density2 / (energy_density1 + energy_density2)) as BTU
+	}
+
+	fn co2_per_unit() -> u32 {
+		(F1::co2_per_unit() + F2::co2_per_unit()) / 2
+	}
+
+	fn cost_per_unit() -> u32 {
+		(F1::cost_per_unit() + F2::cost_per_unit()) / 2
+	}
+}
+
+/// Like [`Mixed`] and [`HarmonicMixed`], an even mix of two fuel types, but with an energy density
+/// that is the geometric mean of the two, once converted to BTU, and rounded down. The output unit
+/// is BTU.
+pub struct GeoMixed<F1: Fuel, F2: Fuel>(PhantomData<(F1, F2)>);
+impl<F1: Fuel, F2: Fuel> Fuel for GeoMixed<F1, F2> {
+	type Output = BTU;
+
+	fn energy_density() -> Self::Output {
+		let energy_density1 = BTU::from(F1::energy_density().into()) as u64;
+		let energy_density2 = BTU::from(F2::energy_density().into()) as u64;
+
+		isqrt(energy_density1 * energy_density2) as BTU
+	}
+
+	fn co2_per_unit() -> u32 {
+		(F1::co2_per_unit() + F2::co2_per_unit()) / 2
+	}
+
+	fn cost_per_unit() -> u32 {
+		(F1::cost_per_unit() + F2::cost_per_unit()) / 2
+	}
+}
+
+/// The integer square root of `n`, rounded down.
+fn isqrt(n: u64) -> u64 {
+	// binary search for the largest `root` with `root * root <= n`, which is at most `u32::MAX`.
+	let (mut low, mut high) = (0u64, u32::MAX as u64 + 1);
+	while high - low > 1 {
+		let mid = low + (high - low) / 2;
+		if mid * mid <= n {
+			low = mid;
+		} else {
+			high = mid;
+		}
+	}
+	low
+}
+
+/// The smallest coefficient `C` such that, in `CustomMixed<C, R, N>`, at least
+/// `min_renewable_energy_pct` percent of the energy comes from the renewable `R`. Note that this
+/// is by energy, not by units, so the differing densities of the two fuels are accounted for.
+///
+/// Like efficiencies, the percentage saturates at 100.
+pub fn min_renewable_ratio<R: Fuel + IsRenewable, N: Fuel>(min_renewable_energy_pct: u8) -> u8 {
+	let renewable_density = BTU::from(R::energy_density().into()) as u64;
+	let other_density = BTU::from(N::energy_density().into()) as u64;
+	let pct = min_renewable_energy_pct.min(100) as u64;
+
+	(0..=100u8)
+		.find(|&c| {
+			let renewable = c as u64 * renewable_density;
+			let other = (100 - c) as u64 * other_density;
+			100 * renewable >= pct * (renewable + other)
+		})
+		.expect("c = 100 always satisfies the bound")
+}
+
+/// The efficiency, in percent and rounded up, at which `amount` units of the non-renewable `N`
+/// yield as much energy as `amount` units of the renewable `R` at an ideal efficiency of 100%.
+///
+/// Unlike elsewhere, this does not saturate at 100: anything above it means that no upgrade of an
+/// `N` provider can match `R`. It saturates at `u8::MAX` instead, which is also returned if `N`
+/// has no energy density at all. No fuel, or no energy to match, needs an efficiency of zero.
+pub fn equivalent_efficiency<R: Fuel + IsRenewable, N: Fuel>(amount: u32) -> u8 {
+	let renewable = BTU::from(R::energy_density().into()) as u128 * amount as u128;
+	let other = BTU::from(N::energy_density().into()) as u128 * amount as u128;
+	if renewable == 0 {
+		return 0
+	}
+	if other == 0 {
+		return u8::MAX
+	}
+
+	((100 * renewable + other - 1) / other).min(u8::MAX as u128) as u8
+}
+
+/// The energy densities of the even [`Mixed`], [`HarmonicMixed`] and [`GeoMixed`] blends of `F1`
+/// and `F2`, in that order, e.g. for comparing the blend strategies.
+pub fn compare_blends<F1: Fuel, F2: Fuel>() -> (BTU, BTU, BTU) {
+	(
+		Mixed::<F1, F2>::energy_density(),
+		HarmonicMixed::<F1, F2>::energy_density(),
+		GeoMixed::<F1, F2>::energy_density(),
+	)
+}
+
+/// The CO2-equivalent emitted by burning `amount` units of `F`, saturating at `u32::MAX`.
+pub fn emissions<F: Fuel>(amount: u32) -> u32 {
+	amount.saturating_mul(F::co2_per_unit())
+}
+
+/// The CO2-equivalent emitted by burning `amount` units of [`CustomMixed`] with a coefficient of
+/// `c`, which saturates at 100 like efficiencies do. The emissions saturate at `u32::MAX`.
+///
+/// Unlike `emissions::<CustomMixed<C, F1, F2>>`, this does not round the emissions of each unit,
+/// but only the total.
+pub fn blend_emissions<F1: Fuel, F2: Fuel>(c: u8, amount: u32) -> u32 {
+	let c = c.min(100);
+	let co2_percent =
+		F1::co2_per_unit() as u128 * c as u128 + F2::co2_per_unit() as u128 * (100 - c) as u128;
+	(amount as u128 * co2_percent / 100).min(u32::MAX as u128) as u32
+}
+
+// Now, any of our existing energy providers can be used with a mix fuel.
+
+/// A function that returns the energy produced by the `OmniGenerator` with efficiency of 80%, when
+/// the fuel type is an even a mix of `Diesel` as `LithiumBattery`;
+pub fn omni_80_energy(amount: u32) -> BTU {
+	let fuel_container: FuelContainer<Mixed<Diesel, LithiumBattery>> = FuelContainer::new(amount);
+	let og = OmniGenerator::<80>;
+	og.provide_energy(fuel_container).into()
+}
+
+/// The energy that provider `p` produces from `f`, always expressed in [`Joule`], regardless of the
+/// native unit of `F`.
+pub fn provide_energy_as_joules<F: Fuel>(p: &impl ProvideEnergy<F>, f: FuelContainer<F>) -> Joule {
+	let btu: BTU = p.provide_energy(f).into();
+	Joule::from(btu)
+}
+
+/// Run `provider` on `tank` until it is empty, drawing `per_call` units at a time (or whatever is
+/// left for the last call), and return the total energy produced in BTU.
+///
+/// Stateful providers are called once per draw, so any decay accumulates over the drain. Drawing
+/// zero units per call would never empty the tank, so it produces nothing instead.
+pub fn drain<F: Fuel>(
+	provider: &impl ProvideEnergy<F>,
+	mut tank: FuelContainer<F>,
+	per_call: u32,
+) -> BTU {
+	let mut total: BTU = 0;
+	if per_call == 0 {
+		return total
+	}
+
+	while tank.amount > 0 {
+		let draw = per_call.min(tank.amount);
+		tank.amount -= draw;
+		let energy: BTU = provider.provide_energy(FuelContainer::new(draw)).into();
+		total = total.saturating_add(energy);
+	}
+	total
+}
+
+/// The fraction of the energy stored in `tank` that `provider` actually delivers when it
+/// [`drain`]s the tank, `per_call` units at a time. Decaying providers stay below 1.0.
+///
+/// A tank that stores no energy at all cannot be utilized either, so its utilization is 0.0.
+pub fn utilization<F: Fuel>(
+	provider: &impl ProvideEnergy<F>,
+	tank: FuelContainer<F>,
+	per_call: u32,
+) -> f32 {
+	let max = stored_energy(&tank);
+	if max == 0 {
+		return 0.0
+	}
+
+	drain(provider, tank, per_call) as f32 / max as f32
+}
+
+/// The mean energy, in BTU and rounded down, that each of `providers` produces from `amount`
+/// units of fuel. An empty ensemble produces nothing.
+pub fn ensemble_mean<F: Fuel>(providers: &[&dyn ProvideEnergy<F>], amount: u32) -> BTU {
+	if providers.is_empty() {
+		return 0
+	}
+
+	let total: u64 = providers
+		.iter()
+		.map(|p| BTU::from(p.provide_energy(FuelContainer::new(amount)).into()) as u64)
+		.sum();
+	(total / providers.len() as u64) as BTU
+}
+
+/// Split `amount_total` units of fuel across `sources` proportionally to their weights, run each
+/// source on its share, and return the total energy produced in BTU (saturating at `BTU::MAX`).
+///
+/// The shares are rounded such that they always add up to `amount_total` exactly. If all weights
+/// are zero, no fuel is burnt at all.
+pub fn weighted_output<F: Fuel>(
+	sources: &[(u32, &dyn ProvideEnergy<F>)],
+	amount_total: u32,
+) -> BTU {
+	let total_weight: u64 = sources.iter().map(|&(weight, _)| weight as u64).sum();
+	if total_weight == 0 {
+		return 0
+	}
+
+	// each share is the difference of the rounded cumulative shares, so that no unit gets lost.
+	let mut cumulative_weight: u64 = 0;
+	let mut allocated: u64 = 0;
+	sources.iter().fold(0, |total: BTU, &(weight, source)| {
+		cumulative_weight += weight as u64;
+		let cumulative_amount = cumulative_weight * amount_total as u64 / total_weight;
+		let share = (cumulative_amount - allocated) as u32;
+		allocated = cumulative_amount;
+
+		let energy: BTU = source.provide_energy(FuelContainer::new(share)).into();
+		total.saturating_add(energy)
+	})
+}
+
+/// Split the fuel in `tank`, shared by two providers, such that `a` gets `split` percent of it
+/// (rounded down, and at most all of it) and `b` gets the rest. Returns the energy, in BTU, that
+/// each of them produces from its share.
+pub fn dual_draw<F: Fuel>(
+	tank: FuelContainer<F>,
+	a: &impl ProvideEnergy<F>,
+	b: &impl ProvideEnergy<F>,
+	split: u8,
+) -> (BTU, BTU) {
+	let share_a = (tank.amount as u64 * split.min(100) as u64 / 100) as u32;
+	let share_b = tank.amount - share_a;
+	(
+		a.provide_energy(FuelContainer::new(share_a)).into(),
+		b.provide_energy(FuelContainer::new(share_b)).into(),
+	)
+}
+
+/// The index of the bottleneck of a chain of providers in series, that is, the stage with the
+/// lowest of `stage_efficiencies`. Ties go to the earliest stage, and an empty chain has none.
+pub fn bottleneck_stage(stage_efficiencies: &[u8]) -> Option<usize> {
+	stage_efficiencies
+		.iter()
+		.enumerate()
+		.min_by_key(|&(_, efficiency)| efficiency)
+		.map(|(index, _)| index)
+}
+
+/// The efficiency of a plant made of `stages` in parallel, each given as
+/// `(efficiency, throughput)`: the average of the stage efficiencies, weighted by their throughput
+/// and rounded down. Unlike for stages in series, the efficiencies are not multiplied. A plant
+/// without any throughput has an efficiency of zero.
+pub fn weighted_plant_efficiency(stages: &[(u8, u32)]) -> u8 {
+	let total_throughput: u64 = stages.iter().map(|&(_, throughput)| throughput as u64).sum();
+	if total_throughput == 0 {
+		return 0
+	}
+
+	let weighted: u64 = stages
+		.iter()
+		.map(|&(efficiency, throughput)| efficiency.min(100) as u64 * throughput as u64)
+		.sum();
+	(weighted / total_throughput) as u8
+}
+
+/// The mean [`ProvideEnergy::nominal_efficiency`] of `fleet`, rounded down. An empty fleet has an
+/// efficiency of zero.
+pub fn fleet_average_efficiency<F: Fuel>(fleet: &[&dyn ProvideEnergy<F>]) -> u8 {
+	if fleet.is_empty() {
+		return 0
+	}
+
+	let total: u32 = fleet.iter().map(|p| p.nominal_efficiency().min(100) as u32).sum();
+	(total / fleet.len() as u32) as u8
+}
+
+/// The energy, in BTU, that `p` produces from each of `amounts`, in order.
+///
+/// Stateful providers are called once per amount, so each entry reflects the state left behind by
+/// the ones before it.
+pub fn energy_series<F: Fuel>(p: &impl ProvideEnergy<F>, amounts: &[u32]) -> Vec<BTU> {
+	amounts.iter().map(|&amount| p.provide_energy(FuelContainer::new(amount)).into()).collect()
+}
+
+/// Like [`energy_series`], but lazy: the energy, in BTU, of each of `deliveries` is only produced
+/// once the returned iterator gets to it, so that stateful providers are called in order.
+pub fn energy_stream<'a, F: Fuel + 'a, I: Iterator<Item = FuelContainer<F>> + 'a>(
+	provider: &'a impl ProvideEnergy<F>,
+	deliveries: I,
+) -> impl Iterator<Item = BTU> + 'a {
+	deliveries.map(move |f| provider.provide_energy(f).into())
+}
+
+/// Run each of the named `providers` on `amount` units of fuel, and return their names along with
+/// the produced energy in BTU, most productive first. Ties keep their original order.
+pub fn rank_providers<'a, F: Fuel>(
+	providers: &[(&'a str, &dyn ProvideEnergy<F>)],
+	amount: u32,
+) -> Vec<(&'a str, BTU)> {
+	let mut ranking: Vec<(&str, BTU)> = providers
+		.iter()
+		.map(|&(name, p)| (name, p.provide_energy(FuelContainer::new(amount)).into()))
+		.collect();
+	ranking.sort_by_key(|&(_, energy)| std::cmp::Reverse(energy));
+	ranking
+}
+
+// Finally, let's consider marker traits, and some trait bounds.
+
+/// Some traits are just markers. They don't bring any additional functionality anything, other than
+/// marking a type with some trait.
+pub trait IsRenewable {}
+impl IsRenewable for LithiumBattery {}
+impl IsRenewable for Hydrogen {}
+
+/// A blend of two renewable fuels is itself renewable.
+impl<F1: Fuel + IsRenewable, F2: Fuel + IsRenewable> IsRenewable for Mixed<F1, F2> {}
+
+/// Define the following struct such that it only provides energy if the fuel is `IsRenewable`.
+///
+/// It has perfect efficiency.
+///
+/// A blend of two renewables can be used, but as soon as one side of the blend is not renewable,
+/// this will fail to compile:
+///
+/// ```compile_fail
+/// use pba_qualifier_exam::h_advanced_traits::{Diesel, GreenEngine, LithiumBattery, Mixed};
+/// use std::marker::PhantomData;
+///
+/// # fn main() {
+/// let engine = GreenEngine::<Mixed<LithiumBattery, Diesel>>(PhantomData);
+/// # }
+/// ```
+pub struct GreenEngine<F: Fuel + IsRenewable>(pub PhantomData<F>);
+
+// `#[derive]` would needlessly require `F` itself to be `Default`, `Clone` and `Copy`.
+impl<F: Fuel + IsRenewable> Default for GreenEngine<F> {
+	fn default() -> Self {
+		GreenEngine(PhantomData)
+	}
+}
+impl<F: Fuel + IsRenewable> Clone for GreenEngine<F> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<F: Fuel + IsRenewable> Copy for GreenEngine<F> {}
+
+impl<F: Fuel + IsRenewable> ProvideEnergy<F> for GreenEngine<F> {
+	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+		//todo!("complete the implementation; no te that you might need to change the trait bounds and generics of the `impl` line");
+		self.provide_energy_ideal(f)
+	}
+}
+
+/// Define the following struct such that it only provides energy if the fuel's output type is
+/// `BTU`.
+///
+/// It has perfect efficiency.
+pub struct BritishEngine<F: Fuel<Output = BTU>>(pub PhantomData<F>);
+
+impl<F: Fuel<Output = BTU>> Default for BritishEngine<F> {
+	fn default() -> Self {
+		BritishEngine(PhantomData)
+	}
+}
+impl<F: Fuel<Output = BTU>> Clone for BritishEngine<F> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<F: Fuel<Output = BTU>> Copy for BritishEngine<F> {}
+
+impl<F: Fuel<Output = BTU>> ProvideEnergy<F> for BritishEngine<F> {
+	fn provide_energy(&self, f: FuelContainer<F>) -> <F as Fuel>::Output {
+		//todo!("complete the implementation; note that you might need to change the trait bounds and generics of the `impl` line");
+		self.provide_energy_ideal(f)
+	}
+}
+
+/// The fixed efficiencies of the built-in providers: [`NuclearReactor`], the `OmniGenerator` of
+/// [`omni_80_energy`], and the perfect [`GreenEngine`] and [`BritishEngine`].
+const BUILTIN_EFFICIENCIES: [u8; 3] = [99, 80, 100];
+
+/// The efficiency of the built-in provider that is closest to `target`.
+pub fn closest_provider_efficiency(target: u8) -> u8 {
+	BUILTIN_EFFICIENCIES
+		.iter()
+		.copied()
+		.min_by_key(|e| e.abs_diff(target))
+		.expect("there are built-in providers")
+}
+
+/// Allocate `available` energy across `demands`.
+///
+/// If there is enough energy, every demand is served in full. Otherwise, each demand gets its
+/// proportional share of `available`, rounded down, such that the allocations never sum up to more
+/// than `available`.
+pub fn allocate(available: BTU, demands: &[BTU]) -> Vec<BTU> {
+	let total: u64 = demands.iter().map(|&d| d as u64).sum();
+	if total <= available as u64 {
+		return demands.to_vec()
+	}
+
+	demands
+		.iter()
+		.map(|&d| (d as u64 * available as u64 / total) as BTU)
+		.collect()
+}
+
+/// Serve `demands` from a grid of the given `capacity`, as allocated by [`allocate`].
+///
+/// Also returns whether there was a brownout, that is, whether the total demand exceeded the
+/// capacity, and thus all demands were cut proportionally.
+pub fn serve_demand(capacity: BTU, demands: &[BTU]) -> (Vec<BTU>, bool) {
+	let total: u64 = demands.iter().map(|&d| d as u64).sum();
+	(allocate(capacity, demands), total > capacity as u64)
+}
+
+// Congratulations! you have finished the advance trait section.
+//
+// Disclaimer: the types and traits that you are asked to implement in this module are by no means
+// designed to be sensible. Instead, they are chosen to represent a typical, often difficult,
+// pattern. Some are intentionally slightly convoluted to challenge you :). I am sure if we actually
+// wanted to design a fuel system, we would do better.
+
+/// This function is not graded. It is just for collecting feedback.
+/// The answers to both of the questions below, gathered in one [`crate::Feedback`].
+pub fn feedback() -> crate::Feedback {
+	crate::Feedback { difficulty: 3, hours: 215 }
+}
+
+/// This function is not graded. It is just for collecting feedback.
+/// On a scale from 0 - 255, with zero being extremely easy and 255 being extremely hard,
+/// how hard did you find this section of the exam.
+pub fn how_hard_was_this_section() -> u8 {
+	feedback().difficulty
+}
+
+/// This function is not graded. It is just for collecting feedback.
+/// How much time (in hours) did you spend on this section of the exam?
+pub fn how_many_hours_did_you_spend_on_this_section() -> u8 {
+	feedback().hours
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	trait ToBTU {
+		fn to_btu(self) -> BTU;
+	}
+
+	impl<T: Into<BTU>> ToBTU for T {
+		fn to_btu(self) -> BTU {
+			self.into()
+		}
+	}
+
+	/// Convert each of `values` (expressed in `U`) to BTU and back, and make sure that we only ever
+	/// lose what the integer division in the conversion truncates, i.e. less than one BTU.
+	fn assert_roundtrip<U: EnergyUnit>(values: &[u32]) {
+		for &value in values {
+			let btu: BTU = U::from_raw(value).into();
+			let back = U::from(btu).raw();
+			assert!(back <= value, "{} round-tripped up to {}", value, back);
+			assert!(value - back < U::PER_BTU, "{} round-tripped down to {}", value, back);
+			assert_eq!(back % U::PER_BTU, 0);
+		}
+	}
+
+	/// Make sure that `p` honors the documented saturation of efficiencies at 100%, i.e. that an
+	/// efficiency of 200 yields the same as one of 100.
+	fn assert_efficiency_saturates<F: Fuel>(p: &impl ProvideEnergy<F>, amount: u32) {
+		let saturated = p.provide_energy_with_efficiency(FuelContainer::new(amount), 200);
+		let ideal = p.provide_energy_with_efficiency(FuelContainer::new(amount), 100);
+		assert_eq!(saturated.to_btu(), ideal.to_btu());
+	}
+
+	#[test]
+	fn unit_roundtrip() {
+		let values = [0, 1, 250, 251, 252, 1054, 1055, 1056, 100_000, 4_000_000];
+		assert_roundtrip::<Joule>(&values);
+		assert_roundtrip::<Calorie>(&values);
+	}
+
+	#[test]
+	fn nuclear() {
+		let nr = NuclearReactor;
+		assert_eq!(
+			nr.provide_energy(FuelContainer::<Uranium>::new(10))
+				.to_btu(),
+			9900
+		);
+		assert_eq!(
+			nr.provide_energy(FuelContainer::<Uranium>::new(10))
+				.to_btu(),
+			9900
+		);
+	}
+
+	#[test]
+	fn ic_1() {
+		let ic = InternalCombustion::<3>::new(120);
+		assert_eq!(
+			ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+			1000
+		);
+		assert_eq!(
+			ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+			1000
+		);
+		assert_eq!(
+			ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+			1000
+		);
+		assert_eq!(
+			ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+			990
+		);
+	}
+
+	#[test]
+	fn omni_1() {
+		let og = OmniGenerator::<100>;
+		assert_eq!(
+			og.provide_energy(FuelContainer::<Uranium>::new(10))
+				.to_btu(),
+			10000
+		);
+		assert_eq!(
+			og.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+			1000
+		);
+		assert_eq!(
+			og.provide_energy(FuelContainer::<LithiumBattery>::new(10))
+				.to_btu(),
+			2000
+		);
+	}
+
+	#[test]
+	fn mixed_1() {
+		assert_eq!(
+			Mixed::<Diesel, LithiumBattery>::energy_density().to_btu(),
+			150
+		);
+	}
+
+	#[test]
+	fn custom_mixed_1() {
+		// custom with 50 is the same as Mixed.
+		assert_eq!(
+			CustomMixed::<50, Diesel, LithiumBattery>::energy_density().to_btu(),
+			Mixed::<Diesel, LithiumBattery>::energy_density()
+		);
+	}
+
+	#[test]
+	fn omni_80_1() {
+		let amount = 10; 
+		let expected_btu_output = 1200; 
+		let btu_output = omni_80_energy(amount);
+		assert_eq!(btu_output, expected_btu_output)
+	}
+
+	#[test]
+	fn quality_degrades_efficiency() {
+		let og = OmniGenerator::<100>;
+		assert_eq!(
+			og.provide_energy_quality(FuelContainer::<Diesel>::new(10), 100)
+				.to_btu(),
+			1000
+		);
+		assert_eq!(
+			og.provide_energy_quality(FuelContainer::<Diesel>::new(10), 50)
+				.to_btu(),
+			500
+		);
+	}
+
+	#[test]
+	fn stored_energy_is_ideal() {
+		assert_eq!(stored_energy(&FuelContainer::<Uranium>::new(10)), 10000);
+		assert_eq!(stored_energy(&FuelContainer::<Diesel>::new(0)), 0);
+	}
+
+	#[test]
+	fn green_mixed_renewables() {
+		let ge = GreenEngine::<Mixed<LithiumBattery, Hydrogen>>(PhantomData);
+		assert_eq!(
+			ge.provide_energy(FuelContainer::new(10)).to_btu(),
+			2500
+		);
+	}
+
+	#[test]
+	fn marginal_energy_is_density() {
+		let og = OmniGenerator::<100>;
+		assert_eq!(
+			ProvideEnergy::<Diesel>::marginal_energy(&og, 10),
+			Diesel::energy_density().to_btu()
+		);
+		assert_eq!(
+			ProvideEnergy::<Uranium>::marginal_energy(&og, 0),
+			Uranium::energy_density().to_btu()
+		);
+		// there is no unit beyond `u32::MAX`.
+		let source = FixedRateSource::<500>;
+		assert_eq!(ProvideEnergy::<Hydrogen>::marginal_energy(&source, u32::MAX), 0);
+	}
+
+	#[test]
+	fn warmup_ramps_to_target() {
+		let we = WarmupEngine::<2>::new(97, 99);
+		let outputs = (0..7)
+			.map(|_| we.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu())
+			.collect::<Vec<_>>();
+		assert_eq!(outputs, vec![970, 970, 980, 980, 990, 990, 990]);
+	}
+
+	#[test]
+	fn warmup_without_ramp() {
+		let we = WarmupEngine::<0>::new(97, 99);
+		let outputs = (0..3)
+			.map(|_| we.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu())
+			.collect::<Vec<_>>();
+		assert_eq!(outputs, vec![970, 970, 970]);
+	}
+
+	#[test]
+	fn ratio_for_density_bounds() {
+		assert_eq!(ratio_for_density::<Diesel, LithiumBattery>(100), Some(100));
+		assert_eq!(ratio_for_density::<Diesel, LithiumBattery>(200), Some(0));
+		assert_eq!(ratio_for_density::<Diesel, LithiumBattery>(150), Some(50));
+		assert_eq!(ratio_for_density::<Diesel, LithiumBattery>(99), None);
+		assert_eq!(ratio_for_density::<Diesel, LithiumBattery>(201), None);
+	}
+
+	#[test]
+	fn cumulative_energy_running_totals() {
+		assert_eq!(
+			cumulative_energy::<Diesel>(&[(0, 10), (1, 5), (5, 20)], 50),
+			vec![(0, 500), (1, 750), (5, 1750)]
+		);
+	}
+
+	#[test]
+	fn cumulative_energy_saturates() {
+		assert_eq!(
+			cumulative_energy::<Uranium>(&[(0, 4_000_000), (1, 4_000_000)], 100),
+			vec![(0, 4_000_000_000), (1, BTU::MAX)]
+		);
+		// 5_000_000 units of uranium alone store more than `BTU::MAX`.
+		assert_eq!(cumulative_energy::<Uranium>(&[(0, 5_000_000)], 100), vec![(0, BTU::MAX)]);
+		assert_eq!(
+			cumulative_energy::<Uranium>(&[(0, 5_000_000), (1, 0)], 50),
+			vec![(0, 2_500_000_000), (1, 2_500_000_000)]
+		);
+	}
+
+	#[test]
+	fn no_fuel_yields_nothing() {
+		let og = OmniGenerator::<100>;
+		assert_eq!(og.provide_energy(FuelContainer::<()>::new(10)), 0);
+		let be = BritishEngine::<()>(PhantomData);
+		assert_eq!(be.provide_energy(FuelContainer::new(10)), 0);
+		assert_eq!(stored_energy(&FuelContainer::<()>::new(u32::MAX)), 0);
+	}
+
+	#[test]
+	fn as_joules_from_calories() {
+		let ge = GreenEngine::<LithiumBattery>(PhantomData);
+		assert_eq!(
+			provide_energy_as_joules(&ge, FuelContainer::new(10)),
+			Joule(2000 * 1055)
+		);
+	}
+
+	#[test]
+	fn ic_efficiency_fn() {
+		let ic = InternalCombustion::<3>::new(100);
+		let schedule = |call: u32| if call % 2 == 0 { 100 } else { 50 };
+		let outputs = (0..4)
+			.map(|_| ic.provide_energy_fn(FuelContainer::new(10), schedule).to_btu())
+			.collect::<Vec<_>>();
+		assert_eq!(outputs, vec![1000, 500, 1000, 500]);
+	}
+
+	#[test]
+	fn convert_energy_preserves_btu() {
+		let uranium = FuelContainer::<Uranium>::new(10);
+		let diesel: FuelContainer<Diesel> = convert_energy(uranium);
+		assert_eq!(diesel.amount, 100);
+		assert_eq!(stored_energy(&diesel), 10000);
+
+		// 1000 BTU is not a multiple of 300 BTU, so we round down.
+		let hydrogen: FuelContainer<Hydrogen> = convert_energy(FuelContainer::<Uranium>::new(1));
+		assert_eq!(stored_energy(&hydrogen), 900);
+
+		let nothing: FuelContainer<()> = convert_energy(FuelContainer::<Uranium>::new(1));
+		assert_eq!(nothing.amount, 0);
+	}
+
+	#[test]
+	fn ic_health() {
+		let ic = InternalCombustion::<1>::new(10);
+		assert!(ic.is_healthy());
+		for _ in 0..3 {
+			ic.provide_energy(FuelContainer::new(10));
+		}
+		// decayed to 8, which is still 80% of where we started.
+		assert!(ic.is_healthy());
+		ic.provide_energy(FuelContainer::new(10));
+		assert!(!ic.is_healthy());
+	}
+
+	#[test]
+	fn const_density_matches() {
+		assert_eq!(Diesel::energy_density().to_btu(), Diesel::ENERGY_DENSITY_BTU);
+		assert_eq!(LithiumBattery::energy_density().to_btu(), LithiumBattery::ENERGY_DENSITY_BTU);
+		assert_eq!(Uranium::energy_density().to_btu(), Uranium::ENERGY_DENSITY_BTU);
+		assert_eq!(Hydrogen::energy_density().to_btu(), Hydrogen::ENERGY_DENSITY_BTU);
+	}
+
+	#[test]
+	fn fixed_tank_max_energy() {
+		const MAX: BTU = FixedTank::<100, Uranium>::MAX_ENERGY_BTU;
+		assert_eq!(MAX, 100_000);
+		assert_eq!(FixedTank::<100, Uranium>::new(150).amount(), 100);
+	}
+
+	#[test]
+	fn drain_reflects_decay() {
+		let ic = InternalCombustion::<3>::new(100);
+		// three calls at 100%, then two at 99%.
+		assert_eq!(drain(&ic, FuelContainer::new(50), 10), 1000 * 3 + 990 * 2);
+
+		let og = OmniGenerator::<100>;
+		assert_eq!(drain(&og, FuelContainer::<Diesel>::new(25), 10), 2500);
+		assert_eq!(drain(&og, FuelContainer::<Diesel>::new(25), 0), 0);
+	}
+
+	#[test]
+	fn unit_providers_default() {
+		let nr: NuclearReactor = Default::default();
+		let nr_copy = nr;
+		assert_eq!(nr.provide_energy(FuelContainer::new(10)).to_btu(), 9900);
+		assert_eq!(nr_copy.provide_energy(FuelContainer::new(10)).to_btu(), 9900);
+
+		let og: OmniGenerator<80> = Default::default();
+		assert_eq!(og.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(), 800);
+
+		let ge: GreenEngine<LithiumBattery> = Default::default();
+		assert_eq!(ge.provide_energy(FuelContainer::new(10)).to_btu(), 2000);
+
+		let be: BritishEngine<Hydrogen> = Default::default();
+		assert_eq!(be.provide_energy(FuelContainer::new(10)), 3000);
+	}
+
+	#[test]
+	fn closest_builtin_efficiency() {
+		assert_eq!(closest_provider_efficiency(98), 99);
+		assert_eq!(closest_provider_efficiency(0), 80);
+		assert_eq!(closest_provider_efficiency(200), 100);
+	}
+
+	#[test]
+	fn capped_density() {
+		assert_eq!(Capped::<500, Diesel>::energy_density(), 100);
+		assert_eq!(Capped::<500, Uranium>::energy_density(), 500);
+	}
+
+	#[test]
+	fn allocate_sufficient() {
+		assert_eq!(allocate(100, &[10, 20, 70]), vec![10, 20, 70]);
+		assert_eq!(allocate(0, &[]), vec![]);
+	}
+
+	#[test]
+	fn allocate_proportional() {
+		assert_eq!(allocate(100, &[100, 300]), vec![25, 75]);
+
+		let allocations = allocate(10, &[7, 7, 7]);
+		assert_eq!(allocations, vec![3, 3, 3]);
+		assert!(allocations.iter().sum::<BTU>() <= 10);
+	}
+
+	#[test]
+	fn ensemble_mean_output() {
+		let full = OmniGenerator::<100>;
+		let half = OmniGenerator::<50>;
+		let ensemble: [&dyn ProvideEnergy<Diesel>; 2] = [&full, &half];
+		assert_eq!(ensemble_mean(&ensemble, 10), 750);
+		assert_eq!(ensemble_mean::<Diesel>(&[], 10), 0);
+	}
+
+	#[test]
+	fn ic_fuel_for_ticks() {
+		let ic = InternalCombustion::<2>::new(100);
+		// efficiencies of 100, 100, 99, 99 and 98 need 10, 10, 11, 11 and 11 units respectively.
+		assert_eq!(ic.fuel_for_ticks(5, 1000), 53);
+
+		// planning does not affect the engine, which then behaves as planned.
+		for amount in [10, 10, 11, 11, 11] {
+			let output = ic.provide_energy(FuelContainer::new(amount)).to_btu();
+			assert!(output >= 1000);
+			assert!(output - (output / amount) < 1000);
+		}
+
+		assert_eq!(InternalCombustion::<2>::new(0).fuel_for_ticks(1, 1), u32::MAX);
+		assert_eq!(InternalCombustion::<2>::new(0).fuel_for_ticks(1, 0), 0);
+	}
+
+	#[test]
+	fn average_of_joules() {
+		assert_eq!(average_joules(&[Joule(10), Joule(20), Joule(40)]), Joule(23));
+		assert_eq!(average_joules(&[Joule(u32::MAX), Joule(u32::MAX)]), Joule(u32::MAX));
+		assert_eq!(average_joules(&[]), Joule(0));
+	}
+
+	#[test]
+	fn reactor_warmup() {
+		let reactor = Reactor::<2>::new();
+		let outputs = (0..4)
+			.map(|_| reactor.provide_energy(FuelContainer::new(10)).to_btu())
+			.collect::<Vec<_>>();
+		assert_eq!(outputs, vec![5000, 5000, 9900, 9900]);
+
+		let cold = Reactor::<0>::new();
+		assert_eq!(cold.provide_energy(FuelContainer::new(10)).to_btu(), 9900);
+	}
+
+	#[test]
+	fn emissions_of_fuels() {
+		assert_eq!(emissions::<Diesel>(10), 740);
+		assert!(emissions::<Uranium>(10) > 0);
+		assert_eq!(emissions::<LithiumBattery>(10), 0);
+		assert_eq!(emissions::<Hydrogen>(10), 0);
+
+		assert_eq!(emissions::<Mixed<Diesel, LithiumBattery>>(10), 370);
+		assert_eq!(emissions::<CustomMixed<25, Diesel, LithiumBattery>>(100), 1800);
+	}
+
+	#[test]
+	fn emissions_of_blends() {
+		// `c` is the share of diesel, so the renewable share rises as it goes down.
+		let by_diesel_share: Vec<u32> = [100, 75, 50, 25, 0]
+			.iter()
+			.map(|&c| blend_emissions::<Diesel, LithiumBattery>(c, 100))
+			.collect();
+		assert_eq!(by_diesel_share, vec![7400, 5550, 3700, 1850, 0]);
+		// only the total is rounded.
+		assert_eq!(blend_emissions::<Diesel, LithiumBattery>(25, 100), 1850);
+		assert_eq!(blend_emissions::<Diesel, LithiumBattery>(25, 1), 18);
+		assert_eq!(blend_emissions::<Diesel, Diesel>(40, u32::MAX), u32::MAX);
+		assert_eq!(blend_emissions::<Diesel, LithiumBattery>(u8::MAX, 100), 7400);
+	}
+
+	#[test]
+	fn nominal_efficiencies() {
+		assert_eq!(NuclearReactor.nominal_efficiency(), 99);
+		assert_eq!(ProvideEnergy::<Diesel>::nominal_efficiency(&OmniGenerator::<80>), 80);
+		assert_eq!(ProvideEnergy::<Diesel>::nominal_efficiency(&OmniGenerator::<200>), 100);
+		assert_eq!(InternalCombustion::<3>::new(90).nominal_efficiency(), 90);
+		assert_eq!(WarmupEngine::<3>::new(50, 90).nominal_efficiency(), 90);
+		assert_eq!(Reactor::<3>::new().nominal_efficiency(), 99);
+		assert_eq!(GreenEngine::<LithiumBattery>::default().nominal_efficiency(), 100);
+
+		// the quality degrades whatever the provider is rated at.
+		assert_eq!(
+			NuclearReactor.provide_energy_quality(FuelContainer::new(10), 50).to_btu(),
+			4900
+		);
+	}
+
+	#[test]
+	fn self_discharging_store() {
+		let store = SelfDischarging::<LithiumBattery>::new(10, 3);
+		store.tick();
+		store.tick();
+		assert_eq!(store.draw().amount, 4);
+		assert_eq!(store.draw().amount, 0);
+
+		let store = SelfDischarging::<LithiumBattery>::new(10, 3);
+		for _ in 0..5 {
+			store.tick();
+		}
+		assert_eq!(store.draw().amount, 0);
+	}
+
+	#[test]
+	fn uranium_diesel_parity() {
+		assert_eq!(energy_parity::<Uranium, Diesel>(), (10, 1));
+		assert_eq!(energy_parity::<Diesel, Uranium>(), (1, 10));
+		assert_eq!(energy_parity::<LithiumBattery, Hydrogen>(), (2, 3));
+		assert_eq!(energy_parity::<Diesel, ()>(), (0, 0));
+	}
+
+	#[test]
+	fn fuel_container_builder() {
+		let container = FuelContainerBuilder::new().fuel::<Diesel>().amount(10).build();
+		assert_eq!(OmniGenerator::<100>.provide_energy(container).to_btu(), 1000);
+
+		// the amount can also be set before the fuel is chosen.
+		let container = FuelContainerBuilder::new().amount(10).fuel::<Uranium>().build();
+		assert_eq!(NuclearReactor.provide_energy(container).to_btu(), 9900);
+	}
+
+	#[test]
+	fn rank_named_providers() {
+		let full = OmniGenerator::<100>;
+		let half = OmniGenerator::<50>;
+		let ic = InternalCombustion::<3>::new(80);
+		let providers: [(&str, &dyn ProvideEnergy<Diesel>); 3] =
+			[("half", &half), ("full", &full), ("ic", &ic)];
+		assert_eq!(
+			rank_providers(&providers, 10),
+			vec![("full", 1000), ("ic", 800), ("half", 500)]
+		);
+	}
+
+	#[test]
+	fn tank_yields_unit_containers() {
+		let tank = Tank::<Diesel>::new(100, 42);
+		let units = (&tank).into_iter().collect::<Vec<_>>();
+		assert_eq!(units.len(), 42);
+		assert!(units.iter().all(|unit| unit.amount == 1));
+		assert_eq!(tank.amount(), 0);
+
+		let tank = Tank::<Diesel>::new(10, 20);
+		let mut drawn = 0;
+		for unit in &tank {
+			drawn += unit.amount;
+		}
+		assert_eq!(drawn, 10);
+	}
+
+	#[test]
+	fn compound_pass_compounds_losses() {
+		let og = OmniGenerator::<50>;
+		let ideal = og.provide_energy_ideal(FuelContainer::<Diesel>::new(100)).to_btu();
+		assert_eq!(og.compound_pass(FuelContainer::<Diesel>::new(100), 2), ideal / 4);
+		assert_eq!(og.compound_pass(FuelContainer::<Diesel>::new(100), 0), ideal);
+		assert_eq!(og.compound_pass(FuelContainer::<()>::new(100), 3), 0);
+	}
+
+	#[test]
+	fn configurable_engine_decay() {
+		let outputs = |config: ProviderConfig| {
+			let engine = ConfigurableEngine::new(config);
+			(0..6)
+				.map(|_| engine.provide_energy(FuelContainer::new(1)).to_btu())
+				.collect::<Vec<_>>()
+		};
+
+		assert_eq!(outputs(ProviderConfig::new(100, 0, 2)), vec![100, 100, 99, 99, 98, 98]);
+		assert_eq!(outputs(ProviderConfig::new(100, 0, 1)), vec![100, 99, 98, 97, 96, 95]);
+		assert_eq!(outputs(ProviderConfig::new(100, 98, 1)), vec![100, 99, 98, 98, 98, 98]);
+		assert_eq!(outputs(ProviderConfig::new(90, 0, 0)), vec![90; 6]);
+		assert_eq!(outputs(ProviderConfig::new(200, 0, 0)), vec![100; 6]);
 	}
-}
 
-// Congratulations! you have finished the advance trait section.
-//
-// Disclaimer: the types and traits that you are asked to implement in this module are by no means
-// designed to be sensible. Instead, they are chosen to represent a typical, often difficult,
-// pattern. Some are intentionally slightly convoluted to challenge you :). I am sure if we actually
-// wanted to design a fuel system, we would do better.
+	#[test]
+	fn same_density_fuels() {
+		assert!(same_density::<Diesel, Diesel>());
+		assert!(!same_density::<Diesel, Uranium>());
+		assert!(same_density::<Hydrogen, Capped<300, Uranium>>());
+	}
 
-/// This function is not graded. It is just for collecting feedback.
-/// On a scale from 0 - 255, with zero being extremely easy and 255 being extremely hard,
-/// how hard did you find this section of the exam.
-pub fn how_hard_was_this_section() -> u8 {
-	3
-}
+	#[test]
+	fn add_mixed_units() {
+		let sum: Joule = add_energy(Joule(2110), Calorie(502));
+		assert_eq!(sum, Joule(4 * 1055));
 
-/// This function is not graded. It is just for collecting feedback.
-/// How much time (in hours) did you spend on this section of the exam?
-pub fn how_many_hours_did_you_spend_on_this_section() -> u8 {
-	215
-}
+		let sum: BTU = add_energy(Calorie(251), 10 as BTU);
+		assert_eq!(sum, 11);
+		assert_eq!(add_energy::<BTU, BTU, BTU>(BTU::MAX, 1), BTU::MAX);
+	}
 
-#[cfg(test)]
-mod tests {
-	use super::*;
+	#[test]
+	fn consume_part_of_tank() {
+		let og = OmniGenerator::<100>;
+		let (energy, rest) = og.consume(FuelContainer::<Diesel>::new(10), 4);
+		assert_eq!((energy.to_btu(), rest.amount), (400, 6));
 
-	trait ToBTU {
-		fn to_btu(self) -> BTU;
-	}
+		let (energy, rest) = og.consume(rest, 100);
+		assert_eq!((energy.to_btu(), rest.amount), (600, 0));
 
-	impl<T: Into<BTU>> ToBTU for T {
-		fn to_btu(self) -> BTU {
-			self.into()
-		}
+		let (energy, rest) = og.consume(rest, 1);
+		assert_eq!((energy.to_btu(), rest.amount), (0, 0));
 	}
 
 	#[test]
-	fn nuclear() {
-		let nr = NuclearReactor;
-		assert_eq!(
-			nr.provide_energy(FuelContainer::<Uranium>::new(10))
-				.to_btu(),
-			9900
-		);
-		assert_eq!(
-			nr.provide_energy(FuelContainer::<Uranium>::new(10))
-				.to_btu(),
-			9900
-		);
+	fn harmonic_vs_arithmetic_mix() {
+		assert_eq!(HarmonicMixed::<Diesel, LithiumBattery>::energy_density(), 133);
+		assert_eq!(Mixed::<Diesel, LithiumBattery>::energy_density(), 150);
+
+		assert_eq!(HarmonicMixed::<Uranium, Uranium>::energy_density(), 1000);
+		assert_eq!(HarmonicMixed::<Uranium, ()>::energy_density(), 0);
 	}
 
 	#[test]
-	fn ic_1() {
-		let ic = InternalCombustion::<3>::new(120);
-		assert_eq!(
-			ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
-			1000
-		);
+	fn describe_blend_composition() {
 		assert_eq!(
-			ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
-			1000
+			describe_blend::<Diesel, LithiumBattery>(45),
+			"45% Diesel (100 BTU) + 55% LithiumBattery (200 BTU) = 155 BTU"
 		);
 		assert_eq!(
-			ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
-			1000
+			describe_blend::<Uranium, Hydrogen>(100),
+			"100% Uranium (1000 BTU) + 0% Hydrogen (300 BTU) = 1000 BTU"
 		);
 		assert_eq!(
-			ic.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
-			990
+			describe_blend::<Uranium, Hydrogen>(u8::MAX),
+			describe_blend::<Uranium, Hydrogen>(100)
 		);
 	}
 
 	#[test]
-	fn omni_1() {
-		let og = OmniGenerator::<100>;
-		assert_eq!(
-			og.provide_energy(FuelContainer::<Uranium>::new(10))
-				.to_btu(),
-			10000
-		);
+	fn fixed_rate_source_output() {
+		let largest = FixedRateSource::<4_071_059>;
+		assert_eq!(largest.provide_energy(FuelContainer::<Diesel>::new(1)).to_btu(), 4_071_059);
+		let largest = FixedRateSource::<17_111_423>;
+		let energy = largest.provide_energy(FuelContainer::<LithiumBattery>::new(1)).to_btu();
+		assert_eq!(energy, 17_111_423);
+
+		let source = FixedRateSource::<500>;
+		for amount in [1, 10, 1000] {
+			assert_eq!(source.provide_energy(FuelContainer::<Diesel>::new(amount)).to_btu(), 500);
+			assert_eq!(source.provide_energy(FuelContainer::<Uranium>::new(amount)).to_btu(), 500);
+		}
+		assert_eq!(source.provide_energy(FuelContainer::<Diesel>::new(0)).to_btu(), 0);
+	}
+
+	#[test]
+	fn container_energy_portfolio() {
+		let portfolio: Vec<Box<dyn ContainerEnergy>> = vec![
+			Box::new(FuelContainer::<Diesel>::new(10)),
+			Box::new(FuelContainer::<Uranium>::new(2)),
+			Box::new(FuelContainer::<Diesel>::new(5)),
+		];
+		assert_eq!(portfolio.iter().map(|c| c.btu()).sum::<BTU>(), 3500);
+	}
+
+	#[test]
+	fn ic_maintenance_break_even() {
+		fn simulated<const DECAY: u32>(efficiency: u8, cost: BTU, amount: u32) -> u32 {
+			let ic = InternalCombustion::<DECAY>::new(efficiency);
+			let serviced = amount * Diesel::ENERGY_DENSITY_BTU * efficiency as u32 / 100;
+			let mut loss = 0;
+			for calls in 1.. {
+				let energy = ic.provide_energy(FuelContainer::new(amount)).to_btu();
+				loss += serviced - energy;
+				if loss > cost {
+					return calls
+				}
+			}
+			unreachable!()
+		}
+
+		let ic = InternalCombustion::<2>::new(100);
+		assert_eq!(ic.maintenance_break_even(50, 10), 6);
+		assert_eq!(ic.maintenance_break_even(50, 10), simulated::<2>(100, 50, 10));
+
+		let ic = InternalCombustion::<1>::new(3);
+		assert_eq!(ic.maintenance_break_even(1000, 100), 7);
+		assert_eq!(ic.maintenance_break_even(1000, 100), simulated::<1>(3, 1000, 100));
+
+		assert_eq!(InternalCombustion::<1>::new(1).maintenance_break_even(0, 100), u32::MAX);
+	}
+
+	#[test]
+	fn ic_maintenance_break_even_slow_decay() {
+		// the first loss is at call 1_000_001, and it takes another 100 of them to exceed the cost.
+		let ic = InternalCombustion::<1_000_000>::new(100);
+		assert_eq!(ic.maintenance_break_even(1000, 10), 1_000_101);
+
+		// no decay happens within `u32::MAX` calls.
+		let ic = InternalCombustion::<{ u32::MAX }>::new(100);
+		assert_eq!(ic.maintenance_break_even(1000, 10), u32::MAX);
+		// without any fuel, nothing is ever lost.
+		let ic = InternalCombustion::<1>::new(100);
+		assert_eq!(ic.maintenance_break_even(0, 0), u32::MAX);
+	}
+
+	#[test]
+	fn provider_config_defaults() {
+		let config = ProviderConfig::from_defaults();
+		assert_eq!(config, ProviderConfig::new(100, 50, 10));
+
+		let engine = ConfigurableEngine::new(config);
+		let outputs = (0..12)
+			.map(|_| engine.provide_energy(FuelContainer::new(1)).to_btu())
+			.collect::<Vec<_>>();
+		assert_eq!(outputs, [vec![100; 10], vec![99; 2]].concat());
+	}
+
+	#[test]
+	fn provider_config_builder() {
 		assert_eq!(
-			og.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
-			1000
+			ProviderConfigBuilder::new().efficiency(80).build(),
+			ProviderConfig::new(80, 50, 10)
 		);
 		assert_eq!(
-			og.provide_energy(FuelContainer::<LithiumBattery>::new(10))
-				.to_btu(),
-			2000
+			ProviderConfigBuilder::new().floor(20).efficiency(200).decay_interval(0).build(),
+			ProviderConfig::new(100, 20, 0)
 		);
 	}
 
 	#[test]
-	fn mixed_1() {
+	fn ic_output_variance() {
+		let ic = InternalCombustion::<2>::new(100);
+		// outputs of 1000, 1000, 990 and 990, with a mean of 995.
+		assert_eq!(ic.output_variance(10, 4), 25);
+		// outputs of 1000, 1000 and 990, with a mean of 996.67.
+		assert_eq!(ic.output_variance(10, 3), 22);
+		assert_eq!(ic.output_variance(10, 1), 0);
+		assert_eq!(ic.output_variance(10, 0), 0);
+
+		// projecting does not affect the engine.
+		assert_eq!(ic.provide_energy(FuelContainer::new(10)).to_btu(), 1000);
+	}
+
+	#[test]
+	fn to_calorie_checked_boundary() {
+		assert_eq!(to_calorie_checked(2), Ok(Calorie(502)));
+		assert_eq!(to_calorie_checked(17_111_423), Ok(Calorie(17_111_423 * 251)));
+		assert_eq!(to_calorie_checked(17_111_424), Err(ConversionError::Overflow));
+		assert_eq!(to_calorie_checked(BTU::MAX), Err(ConversionError::Overflow));
+	}
+
+	#[test]
+	fn ic_energy_series_decays() {
+		let ic = InternalCombustion::<2>::new(100);
+		let series = energy_series(&ic, &[10; 6]);
+		assert_eq!(series, vec![1000, 1000, 990, 990, 980, 980]);
+		assert!(series.windows(2).all(|w| w[0] >= w[1]));
+
+		assert_eq!(energy_series::<Diesel>(&OmniGenerator::<50>, &[1, 2, 0]), vec![50, 100, 0]);
+	}
+
+	#[test]
+	fn cheapest_fuel_for_target() {
+		let kinds = [FuelKind::Diesel, FuelKind::Uranium];
+		// 10 units of diesel cost 30, while a single unit of uranium costs 25.
+		assert_eq!(cheapest_for_target(1000, &kinds), Some((FuelKind::Uranium, 1, 25)));
+		// 1 unit of diesel costs 3, while a single unit of uranium still costs 25.
+		assert_eq!(cheapest_for_target(100, &kinds), Some((FuelKind::Diesel, 1, 3)));
+		assert_eq!(cheapest_for_target(1000, &[]), None);
+
+		assert_eq!(FuelKind::Hydrogen.units_for_target(301), 2);
+		assert_eq!(FuelKind::Hydrogen.units_for_target(0), 0);
+	}
+
+	#[test]
+	fn rate_limited_per_call() {
+		let limited = RateLimited::new(OmniGenerator::<100>, 500);
+		assert_eq!(limited.provide_energy(FuelContainer::<Diesel>::new(3)).to_btu(), 300);
+		assert_eq!(limited.provide_energy(FuelContainer::<Diesel>::new(5)).to_btu(), 500);
+		for _ in 0..10 {
+			assert_eq!(limited.provide_energy(FuelContainer::<Diesel>::new(50)).to_btu(), 500);
+		}
+
+		let limited = RateLimited::new(InternalCombustion::<1>::new(100), 990);
+		let outputs = energy_series(&limited, &[10, 10, 10]);
+		assert_eq!(outputs, vec![990, 990, 980]);
+		assert_eq!(limited.nominal_efficiency(), 100);
+	}
+
+	#[test]
+	fn collect_containers_into_tank() {
+		let deliveries = [10, 20, 30].map(FuelContainer::<Diesel>::new);
+		let tank: Tank<Diesel> = deliveries.into_iter().collect();
+		assert_eq!(tank.amount(), 60);
+
+		let mut tank = Tank::<Diesel>::new(50, 0);
+		tank.extend([10, 20, 30].map(FuelContainer::new));
+		assert_eq!(tank.amount(), 50);
+	}
+
+	#[test]
+	fn cheapest_blend_for_density() {
+		assert_eq!(cheapest_blend::<Diesel, Uranium>(550), Some((50, 14)));
+		assert_eq!(cheapest_blend::<Diesel, Uranium>(1001), None);
+
+		// hydrogen and capped uranium are equally dense, but hydrogen is much cheaper.
+		assert_eq!(cheapest_blend::<Hydrogen, Capped<300, Uranium>>(300), Some((100, 6)));
+		assert_eq!(cheapest_blend::<Capped<300, Uranium>, Hydrogen>(300), Some((0, 6)));
+	}
+
+	#[test]
+	fn serve_demand_normal() {
+		assert_eq!(serve_demand(100, &[10, 20, 70]), (vec![10, 20, 70], false));
+		assert_eq!(serve_demand(100, &[10, 20]), (vec![10, 20], false));
+		assert_eq!(serve_demand(0, &[]), (vec![], false));
+	}
+
+	#[test]
+	fn serve_demand_brownout() {
+		assert_eq!(serve_demand(100, &[100, 300]), (vec![25, 75], true));
+		assert_eq!(serve_demand(100, &[BTU::MAX, BTU::MAX]), (vec![50, 50], true));
+	}
+
+	#[test]
+	fn ic_crossover_efficiency() {
+		let ic = InternalCombustion::<2>::new(100);
+		assert_eq!(ic.crossover_efficiency(10), 100);
+
+		// after 4 calls, the engine has decayed twice.
+		let _ = energy_series(&ic, &[10; 4]);
+		let crossover = ic.crossover_efficiency(10);
+		assert_eq!(crossover, 98);
 		assert_eq!(
-			Mixed::<Diesel, LithiumBattery>::energy_density().to_btu(),
-			150
+			OmniGenerator::<98>.provide_energy(FuelContainer::<Diesel>::new(10)).to_btu(),
+			ic.provide_energy(FuelContainer::new(10)).to_btu()
 		);
 	}
 
 	#[test]
-	fn custom_mixed_1() {
-		// custom with 50 is the same as Mixed.
+	fn geometric_vs_arithmetic_mix() {
+		assert_eq!(GeoMixed::<Diesel, LithiumBattery>::energy_density(), 141);
+		assert_eq!(Mixed::<Diesel, LithiumBattery>::energy_density(), 150);
+		assert_eq!(GeoMixed::<Diesel, Uranium>::energy_density(), 316);
+		assert_eq!(Mixed::<Diesel, Uranium>::energy_density(), 550);
+
+		assert_eq!(GeoMixed::<Uranium, Uranium>::energy_density(), 1000);
+		assert_eq!(GeoMixed::<Uranium, ()>::energy_density(), 0);
+	}
+
+	#[test]
+	fn isqrt_bounds() {
+		assert_eq!(isqrt(0), 0);
+		assert_eq!(isqrt(15), 3);
+		assert_eq!(isqrt(16), 4);
+		assert_eq!(isqrt(u64::MAX), u32::MAX as u64);
+	}
+
+	#[test]
+	fn provider_spec_sheets() {
 		assert_eq!(
-			CustomMixed::<50, Diesel, LithiumBattery>::energy_density().to_btu(),
-			Mixed::<Diesel, LithiumBattery>::energy_density()
+			NuclearReactor.spec_sheet(),
+			ProviderSpec {
+				nominal_efficiency: 99,
+				stateful: false,
+				accepted_fuels: vec!["Uranium"],
+			}
 		);
+
+		let spec = InternalCombustion::<3>::new(80).spec_sheet();
+		assert_eq!((spec.nominal_efficiency, spec.stateful), (80, true));
+		assert_eq!(spec.accepted_fuels, vec!["Diesel"]);
+
+		let spec = ProvideEnergy::<Diesel>::spec_sheet(&OmniGenerator::<200>);
+		assert_eq!((spec.nominal_efficiency, spec.stateful), (100, false));
+		assert_eq!(spec.accepted_fuels, vec!["Any"]);
 	}
 
 	#[test]
-	fn omni_80_1() {
-		let amount = 10; 
-		let expected_btu_output = 1200; 
-		let btu_output = omni_80_energy(amount);
-		assert_eq!(btu_output, expected_btu_output)
+	fn renewable_payback() {
+		// saving 3 * 100 = 300 per period, 1000 is paid back after 4 periods.
+		assert_eq!(payback_units(1000, 3, 100), 4);
+		assert_eq!(payback_units(900, 3, 100), 3);
+		assert_eq!(payback_units(0, 3, 100), 0);
+		assert_eq!(payback_units(1000, 0, 100), u32::MAX);
+		assert_eq!(payback_units(u32::MAX, u32::MAX, u32::MAX), 1);
+	}
+
+	#[test]
+	fn fuel_container_bytes_roundtrip() {
+		let bytes = to_bytes(&FuelContainer::<Diesel>::new(0x01020304));
+		assert_eq!(bytes, vec![0, 4, 3, 2, 1]);
+		assert_eq!(from_bytes(&bytes), Ok((FuelKind::Diesel, 0x01020304)));
+
+		let bytes = to_bytes(&FuelContainer::<Hydrogen>::new(u32::MAX));
+		assert_eq!(from_bytes(&bytes), Ok((FuelKind::Hydrogen, u32::MAX)));
+	}
+
+	#[test]
+	fn fuel_container_bytes_errors() {
+		assert_eq!(from_bytes(&[9, 4, 3, 2, 1]), Err(DecodeError::UnknownTag(9)));
+		assert_eq!(from_bytes(&[0, 4, 3, 2]), Err(DecodeError::WrongLength));
+		assert_eq!(from_bytes(&[0, 4, 3, 2, 1, 0]), Err(DecodeError::WrongLength));
+		assert_eq!(from_bytes(&[]), Err(DecodeError::WrongLength));
+	}
+
+	#[test]
+	fn provide_energy_with_fraction() {
+		let og = OmniGenerator::<100>;
+		let third = og.provide_energy_with_fraction(FuelContainer::<Diesel>::new(300), 1, 3);
+		let percent = og.provide_energy_with_efficiency(FuelContainer::<Diesel>::new(300), 33);
+		assert_eq!((third.to_btu(), percent.to_btu()), (10_000, 9_900));
+
+		let output = og.provide_energy_with_fraction(FuelContainer::<Diesel>::new(300), 3, 2);
+		assert_eq!(output.to_btu(), 30_000);
+		let output = og.provide_energy_with_fraction(FuelContainer::<Diesel>::new(300), 1, 0);
+		assert_eq!(output.to_btu(), 0);
+	}
+
+	#[test]
+	fn fleet_average_nominal_efficiency() {
+		let omni = OmniGenerator::<80>;
+		let ic = InternalCombustion::<3>::new(60);
+		let warmup = WarmupEngine::<3>::new(10, 90);
+		let fleet: [&dyn ProvideEnergy<Diesel>; 3] = [&omni, &ic, &warmup];
+		assert_eq!(fleet_average_efficiency(&fleet), 76);
+		assert_eq!(fleet_average_efficiency::<Diesel>(&[]), 0);
+	}
+
+	#[test]
+	fn mass_container_into_units() {
+		let units = MassContainer::<Diesel>::new(2_550).into_units(250);
+		assert_eq!(units.amount, 10);
+		assert_eq!(OmniGenerator::<50>.provide_energy(units).to_btu(), 500);
+
+		assert_eq!(MassContainer::<Diesel>::new(2_550).into_units(0).amount, 0);
+	}
+
+	#[test]
+	fn weighted_output_split() {
+		let full = OmniGenerator::<100>;
+		let half = OmniGenerator::<50>;
+		// 25 units at 100%, and 75 units at 50%.
+		let sources: [(u32, &dyn ProvideEnergy<Diesel>); 2] = [(1, &full), (3, &half)];
+		assert_eq!(weighted_output(&sources, 100), 2500 + 3750);
+
+		// shares of 3, 3 and 4 units still add up to 10.
+		let sources: [(u32, &dyn ProvideEnergy<Diesel>); 3] = [(1, &full), (1, &full), (1, &full)];
+		assert_eq!(weighted_output(&sources, 10), 1000);
+
+		let sources: [(u32, &dyn ProvideEnergy<Diesel>); 1] = [(0, &full)];
+		assert_eq!(weighted_output(&sources, 10), 0);
+	}
+
+	#[test]
+	fn dual_draw_shared_tank() {
+		let full = OmniGenerator::<100>;
+		let half = OmniGenerator::<50>;
+		// 60 units at 100%, and 40 units at 50%.
+		let tank = FuelContainer::<Diesel>::new(100);
+		assert_eq!(dual_draw(tank, &full, &half, 60), (6000, 2000));
+
+		// the shares of 6 and 5 units still add up to 11.
+		let tank = FuelContainer::<Diesel>::new(11);
+		assert_eq!(dual_draw(tank, &full, &full, 60), (600, 500));
+
+		let tank = FuelContainer::<Diesel>::new(10);
+		assert_eq!(dual_draw(tank, &full, &half, 200), (1000, 0));
+	}
+
+	#[test]
+	fn uranium_max_theoretical_output() {
+		assert_eq!(max_theoretical_output::<Uranium>(7), Uranium::ENERGY_DENSITY_BTU * 7);
+		let reactor = NuclearReactor.provide_energy(FuelContainer::new(7)).to_btu();
+		assert!(reactor < max_theoretical_output::<Uranium>(7));
+	}
+
+	#[test]
+	fn seasonal_output() {
+		let pct = [20, 30, 50, 70, 90, 100, 100, 90, 70, 50, 30, 20];
+		let solar = Seasonal::new(GreenEngine::<Hydrogen>(PhantomData), pct);
+		assert_eq!(solar.provide_energy(FuelContainer::new(10)), 600);
+
+		assert_eq!(solar.set_month(5), Ok(()));
+		assert_eq!(solar.provide_energy(FuelContainer::new(10)), 3000);
+		assert_eq!(solar.set_month(11), Ok(()));
+		assert_eq!(solar.provide_energy(FuelContainer::new(10)), 600);
+	}
+
+	#[test]
+	fn seasonal_bad_month() {
+		let pct = [20, 30, 50, 70, 90, 100, 100, 90, 70, 50, 30, 20];
+		let solar = Seasonal::new(GreenEngine::<Hydrogen>(PhantomData), pct);
+		assert_eq!(solar.set_month(5), Ok(()));
+
+		assert_eq!(solar.set_month(12), Err(InvalidMonth(12)));
+		assert_eq!(solar.set_month(u8::MAX), Err(InvalidMonth(u8::MAX)));
+		// still in the month before.
+		assert_eq!(solar.provide_energy(FuelContainer::new(10)), 3000);
+	}
+
+	#[test]
+	fn drain_utilization() {
+		let ic = InternalCombustion::<2>::new(100);
+		let utilization_ic = utilization(&ic, FuelContainer::new(100), 10);
+		assert!(utilization_ic < 1.0);
+		// efficiencies of 100, 100, 99, 99, 98, 98, 97, 97, 96 and 96.
+		assert!((utilization_ic - 0.98).abs() < 1e-6);
+
+		let og = OmniGenerator::<100>;
+		assert_eq!(utilization(&og, FuelContainer::<Diesel>::new(100), 10), 1.0);
+		assert_eq!(utilization(&og, FuelContainer::<Diesel>::new(0), 10), 0.0);
+	}
+
+	#[test]
+	fn series_bottleneck_stage() {
+		assert_eq!(bottleneck_stage(&[90, 70, 85]), Some(1));
+		assert_eq!(bottleneck_stage(&[90, 70, 70]), Some(1));
+		assert_eq!(bottleneck_stage(&[]), None);
+	}
+
+	#[test]
+	fn plant_efficiency_weighted_by_throughput() {
+		assert_eq!(weighted_plant_efficiency(&[(90, 300), (50, 100)]), 80);
+		assert_eq!(weighted_plant_efficiency(&[(90, 100), (50, 300)]), 60);
+		assert_eq!(weighted_plant_efficiency(&[(75, 10)]), 75);
+		assert_eq!(weighted_plant_efficiency(&[(90, 0), (50, 0)]), 0);
+		assert_eq!(weighted_plant_efficiency(&[]), 0);
+	}
+
+	#[test]
+	fn dynamic_fuel_density() {
+		let og = OmniGenerator::<50>;
+		assert_eq!(og.provide_energy(FuelContainer::<DynamicFuel>::new(10)), 0);
+
+		set_dynamic_density(100);
+		assert_eq!(og.provide_energy(FuelContainer::<DynamicFuel>::new(10)), 500);
+		set_dynamic_density(40);
+		assert_eq!(og.provide_energy(FuelContainer::<DynamicFuel>::new(10)), 200);
+
+		let other_thread = std::thread::spawn(DynamicFuel::energy_density).join().unwrap();
+		assert_eq!(other_thread, 0);
+	}
+
+	#[test]
+	fn fuel_marginal_cost() {
+		assert_eq!(marginal_cost::<Diesel>(10), Some(0.03));
+		assert_eq!(marginal_cost::<Diesel>(1000), Some(0.03));
+		assert_eq!(marginal_cost::<Uranium>(10), Some(0.025));
+		assert_eq!(marginal_cost::<()>(10), None);
+		assert_eq!(marginal_cost::<Capped<0, Diesel>>(10), None);
+	}
+
+	#[test]
+	fn accumulate_running_total() {
+		let ic = InternalCombustion::<1>::new(100);
+		let mut running = 0;
+		for _ in 0..3 {
+			ic.provide_energy_accumulate(FuelContainer::new(10), &mut running);
+		}
+		assert_eq!(running, 1000 + 990 + 980);
+
+		let mut running = BTU::MAX - 1;
+		let og = OmniGenerator::<100>;
+		og.provide_energy_accumulate(FuelContainer::<Diesel>::new(1), &mut running);
+		assert_eq!(running, BTU::MAX);
+	}
+
+	#[test]
+	fn efficiency_curve_endpoints() {
+		let curve = efficiency_output_curve::<Uranium>(3, &[0, 50, 100]);
+		assert_eq!(curve, vec![(0, 0), (50, 1500), (100, 3000)]);
+		assert_eq!(curve.last().unwrap().1, max_theoretical_output::<Uranium>(3));
+	}
+
+	#[test]
+	fn efficiency_saturates_for_all_providers() {
+		assert_efficiency_saturates(&NuclearReactor, 10);
+		assert_efficiency_saturates(&Reactor::<3>::new(), 10);
+		assert_efficiency_saturates(&InternalCombustion::<3>::new(80), 10);
+		assert_efficiency_saturates(&WarmupEngine::<3>::new(10, 90), 10);
+		assert_efficiency_saturates(&ConfigurableEngine::new(ProviderConfig::from_defaults()), 10);
+		assert_efficiency_saturates::<Diesel>(&OmniGenerator::<80>, 10);
+		assert_efficiency_saturates::<Uranium>(&FixedRateSource::<500>, 10);
+		assert_efficiency_saturates::<Diesel>(&LoadCurveProvider::new(40, 90, 100), 10);
+		assert_efficiency_saturates::<Diesel>(&RateLimited::new(OmniGenerator::<80>, 500), 10);
+		assert_efficiency_saturates::<Diesel>(&Seasonal::new(OmniGenerator::<80>, [50; 12]), 10);
+		assert_efficiency_saturates(&GreenEngine::<Hydrogen>(PhantomData), 10);
+		assert_efficiency_saturates(&BritishEngine::<Mixed<Diesel, Uranium>>(PhantomData), 10);
+	}
+
+	#[test]
+	fn power_over_time() {
+		assert_eq!(energy_from_power(1000, 60), Joule(60_000));
+		assert_eq!(energy_from_power(1000, 0), Joule(0));
+		assert_eq!(energy_from_power(u32::MAX, 2), Joule(u32::MAX));
+	}
+
+	#[test]
+	fn blend_strategies_inequality() {
+		let (arithmetic, harmonic, geometric) = compare_blends::<Diesel, Uranium>();
+		assert_eq!((arithmetic, harmonic, geometric), (550, 181, 316));
+		assert!(arithmetic >= geometric && geometric >= harmonic);
+
+		assert_eq!(compare_blends::<Hydrogen, Hydrogen>(), (300, 300, 300));
+	}
+
+	#[test]
+	fn gravimetric_density() {
+		assert_eq!(density_per_gram::<Uranium>(4), Some(250));
+		assert_eq!(density_per_gram::<Diesel>(3), Some(33));
+		assert_eq!(density_per_gram::<Diesel>(0), None);
+	}
+
+	#[test]
+	fn load_curve_efficiency() {
+		let provider = LoadCurveProvider::new(40, 90, 100);
+		assert_eq!(provider.efficiency_at(0), 40);
+		assert_eq!(provider.efficiency_at(50), 65);
+		assert_eq!(provider.efficiency_at(100), 90);
+		assert_eq!(provider.efficiency_at(200), 90);
+
+		assert_eq!(provider.provide_energy(FuelContainer::<Diesel>::new(0)).to_btu(), 0);
+		assert_eq!(provider.provide_energy(FuelContainer::<Diesel>::new(100)).to_btu(), 9000);
+		assert_eq!(provider.provide_energy(FuelContainer::<Diesel>::new(200)).to_btu(), 18000);
+
+		// curves may slope down, too.
+		assert_eq!(LoadCurveProvider::new(90, 40, 100).efficiency_at(50), 65);
+	}
+
+	#[test]
+	fn btu_f64_conversions() {
+		assert_eq!(btu_to_f64(1055), 1055.0);
+		assert_eq!(btu_from_f64(1055.9), Ok(1055));
+		assert_eq!(btu_from_f64(btu_to_f64(BTU::MAX)), Ok(BTU::MAX));
+		assert_eq!(btu_from_f64(-0.0), Ok(0));
+
+		assert_eq!(btu_from_f64(f64::NAN), Err(ConversionError::NotANumber));
+		assert_eq!(btu_from_f64(-1.0), Err(ConversionError::Negative));
+		assert_eq!(btu_from_f64(BTU::MAX as f64 + 1.0), Err(ConversionError::Overflow));
+		assert_eq!(btu_from_f64(f64::INFINITY), Err(ConversionError::Overflow));
+	}
+
+	#[test]
+	fn min_renewable_blend_ratio() {
+		// a third of the units are lithium, but it is twice as dense as diesel.
+		assert_eq!(min_renewable_ratio::<LithiumBattery, Diesel>(50), 34);
+		assert_eq!(min_renewable_ratio::<LithiumBattery, Diesel>(0), 0);
+		assert_eq!(min_renewable_ratio::<LithiumBattery, Diesel>(100), 100);
+		assert_eq!(min_renewable_ratio::<LithiumBattery, Diesel>(200), 100);
+		assert_eq!(min_renewable_ratio::<LithiumBattery, Uranium>(50), 84);
+	}
+
+	#[test]
+	fn renewable_equivalent_efficiency() {
+		// lithium is twice as dense as diesel, so no diesel engine can keep up.
+		assert_eq!(equivalent_efficiency::<LithiumBattery, Diesel>(10), 200);
+		assert_eq!(equivalent_efficiency::<LithiumBattery, Diesel>(u32::MAX), 200);
+		assert_eq!(equivalent_efficiency::<LithiumBattery, Diesel>(0), 0);
+		assert!(equivalent_efficiency::<LithiumBattery, Uranium>(10) < 100);
+		assert_eq!(equivalent_efficiency::<LithiumBattery, LithiumBattery>(10), 100);
+	}
+
+	#[test]
+	fn ic_energy_stream() {
+		let ic = InternalCombustion::<1>::new(100);
+		let deliveries = [10, 20, 10].into_iter().map(FuelContainer::new);
+		let mut stream = energy_stream(&ic, deliveries);
+
+		// nothing is produced before the stream is consumed.
+		assert_eq!(ic.provide_energy(FuelContainer::new(10)).to_btu(), 1000);
+		assert_eq!(stream.next(), Some(990));
+		assert_eq!(stream.collect::<Vec<_>>(), vec![1960, 970]);
+	}
+
+	#[test]
+	fn conversion_chain_loss() {
+		assert_eq!(chain_loss(Joule(3000), 0), 0);
+		assert_eq!(chain_loss(Joule(3000), 1), 3000 - 2 * 1055);
+
+		let losses = (0..5).map(|hops| chain_loss(Joule(3000), hops)).collect::<Vec<_>>();
+		assert!(losses.windows(2).all(|w| w[0] <= w[1]));
+		assert!(losses.iter().all(|&loss| loss < 1055));
+		assert_eq!(chain_loss(Joule(2110), 5), 0);
+	}
+
+	#[test]
+	fn switchable_generator() {
+		let generator = SwitchableGenerator::new(FuelKind::Diesel, 50);
+		assert_eq!(generator.produce(10), 500);
+
+		generator.set_fuel(FuelKind::Uranium);
+		assert_eq!(generator.produce(10), 5000);
+		generator.set_fuel(FuelKind::Hydrogen);
+		assert_eq!(generator.produce(10), 1500);
 	}
 }
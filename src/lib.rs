@@ -14,3 +14,47 @@ pub mod h_advanced_traits;
 pub mod i_extension_traits;
 pub mod k_macros;
 pub mod m_builder;
+
+/// The feedback about a single section of the exam, as returned by the `feedback` function of each
+/// module. Like the feedback functions, this is not graded.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Feedback {
+	/// On a scale from 0 - 255, with zero being extremely easy and 255 being extremely hard, how
+	/// hard did you find this section of the exam.
+	pub difficulty: u8,
+	/// How much time (in hours) did you spend on this section of the exam?
+	pub hours: u8,
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn feedback_matches_legacy_functions() {
+		macro_rules! assert_feedback {
+			($($module:ident),*) => {
+				$(
+					assert_eq!(
+						$module::feedback(),
+						Feedback {
+							difficulty: $module::how_hard_was_this_section(),
+							hours: $module::how_many_hours_did_you_spend_on_this_section(),
+						},
+					);
+				)*
+			};
+		}
+
+		assert_feedback!(
+			b_multiple_choice,
+			d_pattern_matching,
+			e_common_traits,
+			f_iterators,
+			h_advanced_traits,
+			i_extension_traits,
+			k_macros,
+			m_builder
+		);
+	}
+}
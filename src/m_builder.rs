@@ -8,7 +8,11 @@
 //! We will reuse the types from `e_common_traits` module and create a builder for the [`Employee`]
 //! type.
 
-use crate::e_common_traits::Employee;
+use crate::e_common_traits::{recommended_wage, Employee};
+use std::time::SystemTime;
+
+/// The maximum length of an employee's name, in characters, that our storage can handle.
+pub const MAX_NAME_LEN: usize = 64;
 
 /// First, let's build a naive builder. This builder should allow you to build an [`Employee`],
 /// where the `name` and `uid` must be initialized, but the `experience` and `wage` can be left at
@@ -41,6 +45,7 @@ pub struct EmployeeBuilder {
 	uid: Option<u32>,
 	experience: u32,
 	wage: u32,
+	created: Option<SystemTime>,
 }
 
 impl Default for EmployeeBuilder {
@@ -50,6 +55,7 @@ impl Default for EmployeeBuilder {
 			uid: None,
 			wage: 0,
 			experience: 0,
+			created: None,
 		}
 	}
 }
@@ -61,11 +67,27 @@ impl EmployeeBuilder {
 
 	}
 
+	/// Same as [`EmployeeBuilder::name`], but rejects empty names, and names longer than
+	/// [`MAX_NAME_LEN`] characters.
+	pub fn name_checked(self, name: String) -> Result<Self, BuildError> {
+		match name.chars().count() {
+			0 => Err(BuildError::EmptyName),
+			len if len > MAX_NAME_LEN => Err(BuildError::NameTooLong),
+			_ => Ok(self.name(name)),
+		}
+	}
+
 	pub fn uid(mut self, uid: u32) -> Self {
 		self.uid = uid.into();
 		self
 	}
 
+	/// Same as [`EmployeeBuilder::uid`], but parses the uid from `s` first, e.g. when importing
+	/// from CSV.
+	pub fn uid_str(self, s: &str) -> Result<Self, std::num::ParseIntError> {
+		Ok(self.uid(s.parse()?))
+	}
+
 	pub fn experience(mut self, experience: u32) -> Self {
 		self.experience = experience.into();
 		self
@@ -76,7 +98,42 @@ impl EmployeeBuilder {
 		self
 	}
 
+	/// Set both the experience and the wage at once.
+	pub fn package(self, pkg: CompensationPackage) -> Self {
+		self.experience(pkg.experience).wage(pkg.wage)
+	}
+
+	/// Set the wage to the [`recommended_wage`] for the experience set so far. Thus, this must be
+	/// called after [`EmployeeBuilder::experience`], as a later change of experience does not
+	/// update the wage.
+	pub fn auto_wage(self, base: u32, per_year: u32) -> Self {
+		let wage = recommended_wage(self.experience, base, per_year);
+		self.wage(wage)
+	}
+
+	/// The name set so far, if any, without ending the chain.
+	pub fn peek_name(&self) -> Option<&str> {
+		self.name.as_deref()
+	}
+
+	/// The uid set so far, if any, without ending the chain.
+	pub fn peek_uid(&self) -> Option<u32> {
+		self.uid
+	}
+
+	/// Record the current instant as the creation time of the employee, for audit trails. See
+	/// [`EmployeeBuilder::build_record`].
+	pub fn created_now(mut self) -> Self {
+		self.created = Some(SystemTime::now());
+		self
+	}
+
 	pub fn build(self) -> Result<Employee, ()> {
+		self.try_build().map_err(|_| ())
+	}
+
+	/// Same as [`EmployeeBuilder::build`], but tells which of the required fields is missing.
+	pub fn try_build(self) -> Result<Employee, BuildError> {
 		match (self.name, self.uid) {
             (Some(name), Some(uid)) => Ok(Employee {
                 name,
@@ -84,9 +141,95 @@ impl EmployeeBuilder {
                 experience: self.experience,
                 wage: self.wage,
             }),
-            _ => Err(())
+            (None, _) => Err(BuildError::MissingName),
+            (_, None) => Err(BuildError::MissingUid),
         }
 	}
+
+	/// Same as [`EmployeeBuilder::build`], but wraps the employee in an [`EmployeeRecord`] along
+	/// with its creation time. Unless recorded earlier through [`EmployeeBuilder::created_now`],
+	/// that is the time of this call. Returns `None` where `build` fails.
+	pub fn build_record(self) -> Option<EmployeeRecord> {
+		let created = self.created.unwrap_or_else(SystemTime::now);
+		self.build().ok().map(|employee| EmployeeRecord { employee, created })
+	}
+
+	/// Build one [`Employee`] per `(name, uid)` pair, zipping `names` and `uids` together.
+	///
+	/// `experience` and `wage` are left at their default values. Fails if the two vectors have
+	/// different lengths, or if any of the uids is zero.
+	pub fn build_many(names: Vec<String>, uids: Vec<u32>) -> Result<Vec<Employee>, BuildError> {
+		if names.len() != uids.len() {
+			return Err(BuildError::LengthMismatch)
+		}
+
+		names
+			.into_iter()
+			.zip(uids)
+			.map(|(name, uid)| {
+				if uid == 0 {
+					return Err(BuildError::ZeroUid)
+				}
+				Ok(Employee { name, uid, experience: 0, wage: 0 })
+			})
+			.collect()
+	}
+
+	/// Build all of `builders`, e.g. of a form submission. Unlike [`EmployeeBuilder::build_many`],
+	/// this does not stop at the first failure, but reports all of them, along with the index of
+	/// the failing builder.
+	pub fn validate_all(
+		builders: Vec<EmployeeBuilder>,
+	) -> Result<Vec<Employee>, Vec<(usize, BuildError)>> {
+		let mut employees = Vec::new();
+		let mut errors = Vec::new();
+		for (index, builder) in builders.into_iter().enumerate() {
+			match builder.try_build() {
+				Ok(employee) => employees.push(employee),
+				Err(error) => errors.push((index, error)),
+			}
+		}
+
+		if errors.is_empty() {
+			Ok(employees)
+		} else {
+			Err(errors)
+		}
+	}
+}
+
+/// The experience and wage of an [`Employee`], bundled such that they cannot be mixed up when set
+/// through [`EmployeeBuilder::package`] or [`TypedEmployeeBuilder::package`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct CompensationPackage {
+	/// Amount of experience (in months).
+	pub experience: u32,
+	/// Hourly wage.
+	pub wage: u32,
+}
+
+/// The reasons for which building one or more [`Employee`]s can fail.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BuildError {
+	/// The inputs that were supposed to be zipped together had different lengths.
+	LengthMismatch,
+	/// A uid of zero was supplied, which is reserved for unidentified employees.
+	ZeroUid,
+	/// The name is longer than [`MAX_NAME_LEN`] characters.
+	NameTooLong,
+	/// The name is empty.
+	EmptyName,
+	/// No name was set.
+	MissingName,
+	/// No uid was set.
+	MissingUid,
+}
+
+/// An [`Employee`] along with when it was created, for audit trails.
+#[derive(Debug)]
+pub struct EmployeeRecord {
+	pub employee: Employee,
+	pub created: SystemTime,
 }
 
 // Okay, that was good, but the unfortunate thing about the previous approach is that we will have
@@ -103,6 +246,23 @@ impl EmployeeBuilder {
 /// A unique type explicitly representing an employee that has been named.
 pub trait NameGetter {
 	fn get_name(&self) -> String;
+
+	/// Same as [`NameGetter::get_name`], but consumes `self` so that the name can be moved out
+	/// rather than cloned.
+	fn into_name(self) -> String
+	where
+		Self: Sized,
+	{
+		self.get_name()
+	}
+
+	/// The name, if there is one at all.
+	fn name_if_set(self) -> Option<String>
+	where
+		Self: Sized,
+	{
+		Some(self.into_name())
+	}
 }
 pub struct Named {
 	name: String,
@@ -111,6 +271,10 @@ impl NameGetter for Named {
     fn get_name(&self) -> String {
         self.name.clone()
     }
+
+	fn into_name(self) -> String {
+		self.name
+	}
 }
 /// A unique type explicitly representing an employee that NOT has been named.
 pub struct NotNamed;
@@ -118,10 +282,19 @@ impl NameGetter for NotNamed {
     fn get_name(&self) -> String {
 		String::new()
     }
+
+	fn name_if_set(self) -> Option<String> {
+		None
+	}
 }
 /// A unique type explicitly representing an employee that has been identified.
 pub trait IdGetter {
 	fn get_id(&self) -> u32;
+
+	/// The uid, if there is one at all.
+	fn id_if_set(&self) -> Option<u32> {
+		Some(self.get_id())
+	}
 }
 pub struct Identified {
 	uid: u32,
@@ -138,6 +311,10 @@ impl IdGetter for UnIdentified {
     fn get_id(&self) -> u32 {
 		0
     }
+
+	fn id_if_set(&self) -> Option<u32> {
+		None
+	}
 }
 /// A new builder that uses the "type-state" pattern to ensure that the user has set the name and
 /// uid. The main trick here is that instead of having `name` be represented by `Option<String>`, we
@@ -181,63 +358,66 @@ where
     Name: NameGetter,
     Id: IdGetter
 {
-	experience: u32,
-	wage: u32,
+	experience: Tracked<u32>,
+	wage: Tracked<u32>,
 	name: Name,
 	uid: Id,
 }
 
+/// The value of an optional field of the [`TypedEmployeeBuilder`], which remembers whether it was
+/// set explicitly, or left at its default.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Tracked<T> {
+	Unset,
+	Set(T),
+}
+
+impl<T: Default> Tracked<T> {
+	fn is_set(&self) -> bool {
+		matches!(self, Tracked::Set(_))
+	}
+
+	fn value(self) -> T {
+		match self {
+			Tracked::Set(value) => value,
+			Tracked::Unset => T::default(),
+		}
+	}
+}
+
 impl Default for TypedEmployeeBuilder<NotNamed, UnIdentified> {
 	fn default() -> Self {
 		TypedEmployeeBuilder {
-			experience: 0,
-			wage: 0,
+			experience: Tracked::Unset,
+			wage: Tracked::Unset,
 			name: NotNamed,
 			uid: UnIdentified,
 		}
 	}
 }
 
-impl TypedEmployeeBuilder<NotNamed, UnIdentified> 
-{
-	pub fn name(self, name: String) -> TypedEmployeeBuilder<Named, UnIdentified> {
-        TypedEmployeeBuilder {
-            experience: self.experience,
-            wage: self.wage,
-            name: Named { name },
-            uid: self.uid,
-        }
-    }
-	pub fn uid(self, uid: u32) -> TypedEmployeeBuilder<NotNamed, Identified> {
-        TypedEmployeeBuilder {
-            experience: self.experience,
-            wage: self.wage,
-            name:  self.name ,
-            uid: Identified {uid},
-        }
-    }
-}
-impl TypedEmployeeBuilder<Named, UnIdentified> {
-    pub fn uid(self, uid: u32) -> TypedEmployeeBuilder<Named, Identified> {
-        TypedEmployeeBuilder {
-            experience: self.experience,
-            wage: self.wage,
-            name: self.name,
-            uid: Identified { uid },
-        }
-    }
+// The name and uid can each be set exactly once, regardless of whether the other one has already
+// been set, such that all orderings of the setters are possible.
+impl<Id: IdGetter> TypedEmployeeBuilder<NotNamed, Id> {
+	pub fn name(self, name: String) -> TypedEmployeeBuilder<Named, Id> {
+		TypedEmployeeBuilder {
+			experience: self.experience,
+			wage: self.wage,
+			name: Named { name },
+			uid: self.uid,
+		}
+	}
 }
 
-impl TypedEmployeeBuilder<NotNamed, Identified> {
-    pub fn name(self, name: String) -> TypedEmployeeBuilder<Named, Identified> {
-        TypedEmployeeBuilder {
-            experience: self.experience,
-            wage: self.wage,
-            name: Named { name },
-            uid:  self.uid ,
-        }
-    }
-	
+impl<Name: NameGetter> TypedEmployeeBuilder<Name, UnIdentified> {
+	pub fn uid(self, uid: u32) -> TypedEmployeeBuilder<Name, Identified> {
+		TypedEmployeeBuilder {
+			experience: self.experience,
+			wage: self.wage,
+			name: self.name,
+			uid: Identified { uid },
+		}
+	}
 }
 
 impl<Name, Id> TypedEmployeeBuilder<Name, Id> 
@@ -245,36 +425,351 @@ where
     Name: NameGetter,
     Id: IdGetter,{
     pub fn experience(mut self, experience: u32) -> Self {
-        self.experience = experience;
+        self.experience = Tracked::Set(experience);
         self
     }
 
     pub fn wage(mut self, wage: u32) -> Self {
-        self.wage = wage;
+        self.wage = Tracked::Set(wage);
         self
     }
+
+	/// Set both the experience and the wage at once.
+	pub fn package(self, pkg: CompensationPackage) -> Self {
+		self.experience(pkg.experience).wage(pkg.wage)
+	}
+
+	/// Set the wage to the [`recommended_wage`] for the experience set so far. Unlike the name and
+	/// uid, the experience is not tracked in the type, so this must be called after
+	/// [`TypedEmployeeBuilder::experience`] to take it into account: otherwise, it assumes no
+	/// experience, and a later change of experience does not update the wage.
+	pub fn auto_wage(self, base: u32, per_year: u32) -> Self {
+		let wage = recommended_wage(self.experience.value(), base, per_year);
+		self.wage(wage)
+	}
+
+	/// Fall back to the runtime-validated [`EmployeeBuilder`], carrying over whatever has been set
+	/// so far.
+	pub fn into_untyped(self) -> EmployeeBuilder {
+		EmployeeBuilder {
+			name: self.name.name_if_set(),
+			uid: self.uid.id_if_set(),
+			experience: self.experience.value(),
+			wage: self.wage.value(),
+			created: None,
+		}
+	}
+
+	/// Whether the experience was set explicitly, even if to its default of zero.
+	pub fn experience_was_set(&self) -> bool {
+		self.experience.is_set()
+	}
+
+	/// Whether the wage was set explicitly, even if to its default of zero.
+	pub fn wage_was_set(&self) -> bool {
+		self.wage.is_set()
+	}
+}
+
+impl<Id: IdGetter> TypedEmployeeBuilder<Named, Id> {
+	/// Set the wage based on the name, which is only known once the builder is `Named`.
+	pub fn wage_from_name(mut self, f: impl Fn(&str) -> u32) -> Self {
+		self.wage = Tracked::Set(f(&self.name.name));
+		self
+	}
 }
 
 impl TypedEmployeeBuilder<Named, Identified> {
     pub fn build(self) -> Employee {
         Employee {
-            name: self.name.get_name(),
+            name: self.name.into_name(),
             uid: self.uid.get_id(),
-            experience: self.experience,
-            wage: self.wage,
+            experience: self.experience.value(),
+            wage: self.wage.value(),
         }
     }
 }
 
+/// This function is not graded. It is just for collecting feedback.
+/// The answers to both of the questions below, gathered in one [`crate::Feedback`].
+pub fn feedback() -> crate::Feedback {
+	crate::Feedback { difficulty: 200, hours: 3 }
+}
+
 /// This function is not graded. It is just for collecting feedback.
 /// On a scale from 0 - 255, with zero being extremely easy and 255 being extremely hard,
 /// how hard did you find this section of the exam.
 pub fn how_hard_was_this_section() -> u8 {
-	200
+	feedback().difficulty
 }
 
 /// This function is not graded. It is just for collecting feedback.
 /// How much time (in hours) did you spend on this section of the exam?
 pub fn how_many_hours_did_you_spend_on_this_section() -> u8 {
-	3
+	feedback().hours
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	#[test]
+	fn build_many_matched() {
+		let employees = EmployeeBuilder::build_many(
+			vec!["John".to_string(), "Jane".to_string()],
+			vec![42, 43],
+		)
+		.unwrap();
+
+		assert_eq!(employees.len(), 2);
+		assert_eq!(employees[0].name, "John");
+		assert_eq!(employees[0].uid, 42);
+		assert_eq!(employees[1].name, "Jane");
+		assert_eq!(employees[1].uid, 43);
+		assert!(employees.iter().all(|e| e.experience == 0 && e.wage == 0));
+	}
+
+	#[test]
+	fn build_many_length_mismatch() {
+		assert_eq!(
+			EmployeeBuilder::build_many(vec!["John".to_string()], vec![42, 43]),
+			Err(BuildError::LengthMismatch)
+		);
+	}
+
+	#[test]
+	fn build_many_zero_uid() {
+		assert_eq!(
+			EmployeeBuilder::build_many(vec!["John".to_string()], vec![0]),
+			Err(BuildError::ZeroUid)
+		);
+	}
+
+	#[test]
+	fn typed_builder_any_order() {
+		let check = |e: Employee| {
+			assert_eq!(e.name, "John");
+			assert_eq!(e.uid, 42);
+			assert_eq!(e.experience, 5);
+			assert_eq!(e.wage, 77);
+		};
+
+		check(
+			TypedEmployeeBuilder::default()
+				.wage(77)
+				.name("John".to_string())
+				.experience(5)
+				.uid(42)
+				.build(),
+		);
+		check(
+			TypedEmployeeBuilder::default()
+				.uid(42)
+				.experience(5)
+				.name("John".to_string())
+				.wage(77)
+				.build(),
+		);
+		check(
+			TypedEmployeeBuilder::default()
+				.experience(5)
+				.wage(77)
+				.uid(42)
+				.name("John".to_string())
+				.build(),
+		);
+		check(
+			TypedEmployeeBuilder::default()
+				.name("John".to_string())
+				.uid(42)
+				.experience(5)
+				.wage(77)
+				.build(),
+		);
+	}
+
+	#[test]
+	fn uid_from_str() {
+		let employee = EmployeeBuilder::default()
+			.name("John".to_string())
+			.uid_str("42")
+			.unwrap()
+			.build()
+			.unwrap();
+		assert_eq!(employee.uid, 42);
+
+		assert!(EmployeeBuilder::default().uid_str("forty-two").is_err());
+		assert!(EmployeeBuilder::default().uid_str("-42").is_err());
+	}
+
+	#[test]
+	fn named_into_name_moves() {
+		let named = Named { name: "John".to_string() };
+		assert_eq!(named.get_name(), "John");
+		let ptr = named.name.as_ptr();
+		let name = named.into_name();
+		// the very same allocation was moved out, rather than cloned.
+		assert_eq!(name.as_ptr(), ptr);
+		assert_eq!(NotNamed.into_name(), "");
+
+		let employee = TypedEmployeeBuilder::default().name("John".to_string()).uid(42).build();
+		assert_eq!(employee.name, "John");
+	}
+
+	#[test]
+	fn name_length_bounds() {
+		let longest = "a".repeat(MAX_NAME_LEN);
+		let builder = EmployeeBuilder::default().name_checked(longest.clone()).unwrap();
+		assert_eq!(builder.uid(42).build().unwrap().name, longest);
+
+		assert_eq!(
+			EmployeeBuilder::default().name_checked("a".repeat(MAX_NAME_LEN + 1)).err(),
+			Some(BuildError::NameTooLong)
+		);
+		assert_eq!(
+			EmployeeBuilder::default().name_checked(String::new()).err(),
+			Some(BuildError::EmptyName)
+		);
+	}
+
+	#[test]
+	fn peek_mid_chain() {
+		let builder = EmployeeBuilder::default().name("John".to_string());
+		assert_eq!(builder.peek_name(), Some("John"));
+		assert_eq!(builder.peek_uid(), None);
+
+		let builder = builder.uid(42);
+		assert_eq!(builder.peek_uid(), Some(42));
+
+		let employee = builder.wage(77).build().unwrap();
+		assert_eq!((employee.name.as_str(), employee.uid, employee.wage), ("John", 42, 77));
+	}
+
+	#[test]
+	fn wage_from_name() {
+		let employee = TypedEmployeeBuilder::default()
+			.name("Johnny".to_string())
+			.wage_from_name(|name| name.len() as u32 * 10)
+			.uid(42)
+			.build();
+		assert_eq!(employee.wage, 60);
+
+		let employee = TypedEmployeeBuilder::default()
+			.uid(43)
+			.name("Jo".to_string())
+			.wage(77)
+			.wage_from_name(|name| name.len() as u32)
+			.build();
+		assert_eq!(employee.wage, 2);
+	}
+
+	#[test]
+	fn typed_experience_was_set() {
+		let builder = TypedEmployeeBuilder::default().name("John".to_string()).uid(42);
+		assert!(!builder.experience_was_set());
+		assert_eq!(builder.build().experience, 0);
+
+		let builder = TypedEmployeeBuilder::default().experience(0).name("John".to_string());
+		assert!(builder.experience_was_set());
+		assert!(!builder.wage_was_set());
+		let builder = builder.uid(42);
+		assert!(builder.experience_was_set());
+		assert_eq!(builder.build().experience, 0);
+	}
+
+	#[test]
+	fn compensation_package() {
+		let pkg = CompensationPackage { experience: 12, wage: 77 };
+
+		let builder = EmployeeBuilder::default().name("John".to_string()).uid(42);
+		let employee = builder.package(pkg).build().unwrap();
+		assert_eq!((employee.experience, employee.wage), (12, 77));
+
+		let builder = TypedEmployeeBuilder::default().package(pkg);
+		assert!(builder.experience_was_set() && builder.wage_was_set());
+		let employee = builder.name("John".to_string()).uid(42).build();
+		assert_eq!((employee.experience, employee.wage), (12, 77));
+	}
+
+	#[test]
+	fn typed_into_untyped() {
+		let typed = TypedEmployeeBuilder::default().name("John".to_string()).wage(77);
+		let untyped = typed.into_untyped();
+		assert_eq!(untyped.peek_name(), Some("John"));
+		assert_eq!(untyped.peek_uid(), None);
+		assert!(untyped.build().is_err());
+
+		let untyped = TypedEmployeeBuilder::default().uid(42).wage(77).into_untyped();
+		let employee = untyped.name("John".to_string()).build().unwrap();
+		assert_eq!((employee.name.as_str(), employee.uid, employee.wage), ("John", 42, 77));
+	}
+
+	#[test]
+	fn build_record_timestamp() {
+		let before = SystemTime::now();
+		let builder = EmployeeBuilder::default().name("John".to_string()).uid(42).created_now();
+		let after = SystemTime::now();
+
+		let record = builder.build_record().unwrap();
+		assert_eq!(record.employee.uid, 42);
+		assert!(before <= record.created && record.created <= after);
+
+		let record = EmployeeBuilder::default().name("John".to_string()).uid(42).build_record();
+		let age = SystemTime::now().duration_since(record.unwrap().created).unwrap();
+		assert!(age < std::time::Duration::from_secs(60));
+
+		let missing_uid = EmployeeBuilder::default().name("John".to_string()).build_record();
+		assert!(missing_uid.is_none());
+	}
+
+	#[test]
+	fn auto_wage_from_experience() {
+		let builder = EmployeeBuilder::default().name("John".to_string()).uid(42);
+		let employee = builder.experience(36).auto_wage(10, 2).build().unwrap();
+		assert_eq!(employee.wage, 16);
+
+		let employee = TypedEmployeeBuilder::default()
+			.experience(36)
+			.auto_wage(10, 2)
+			.name("John".to_string())
+			.uid(42)
+			.build();
+		assert_eq!(employee.wage, 16);
+
+		// the wage is not updated by a later change of experience.
+		let builder = TypedEmployeeBuilder::default().auto_wage(10, 2).experience(36);
+		assert_eq!(builder.name("John".to_string()).uid(42).build().wage, 10);
+	}
+
+	#[test]
+	fn try_build_missing_fields() {
+		let missing_name = EmployeeBuilder::default().uid(42).try_build();
+		assert_eq!(missing_name.unwrap_err(), BuildError::MissingName);
+		let missing_uid = EmployeeBuilder::default().name("John".to_string()).try_build();
+		assert_eq!(missing_uid.unwrap_err(), BuildError::MissingUid);
+	}
+
+	#[test]
+	fn validate_all_reports_every_error() {
+		let valid = || EmployeeBuilder::default().name("John".to_string()).uid(42);
+		let builders = vec![
+			valid(),
+			EmployeeBuilder::default().uid(43),
+			valid(),
+			EmployeeBuilder::default().name("Jane".to_string()),
+			EmployeeBuilder::default(),
+		];
+		assert_eq!(
+			EmployeeBuilder::validate_all(builders),
+			Err(vec![
+				(1, BuildError::MissingName),
+				(3, BuildError::MissingUid),
+				(4, BuildError::MissingName),
+			])
+		);
+
+		let employees = EmployeeBuilder::validate_all(vec![valid(), valid()]).unwrap();
+		assert_eq!(employees.len(), 2);
+		assert_eq!(EmployeeBuilder::validate_all(vec![]), Ok(vec![]));
+	}
 }